@@ -0,0 +1,191 @@
+use anchor_lang::prelude::*;
+use treasury::ProposalCounter;
+
+// Aethernaut Client Helpers
+// Off-chain PDA derivation for the treasury program
+
+/// Per-agent proposal counter address
+pub fn proposal_counter_address(treasury: &Pubkey, agent: &Pubkey) -> (Pubkey, u8) {
+    Pubkey::find_program_address(
+        &[b"proposal_counter", treasury.as_ref(), agent.as_ref()],
+        &treasury::ID,
+    )
+}
+
+/// Proposal address for a given agent and sequence number
+pub fn proposal_address(treasury: &Pubkey, agent: &Pubkey, sequence: u64) -> (Pubkey, u8) {
+    Pubkey::find_program_address(
+        &[
+            b"proposal",
+            treasury.as_ref(),
+            agent.as_ref(),
+            &sequence.to_le_bytes(),
+        ],
+        &treasury::ID,
+    )
+}
+
+/// Address the agent's next `submit_decision` will create
+/// Pass `None` when the counter account does not exist yet
+pub fn next_proposal_address(
+    treasury: &Pubkey,
+    agent: &Pubkey,
+    counter: Option<&ProposalCounter>,
+) -> (Pubkey, u8) {
+    let sequence = counter.map_or(0, |counter| counter.next_sequence);
+    proposal_address(treasury, agent, sequence)
+}
+
+/// Addresses of every proposal the agent has submitted so far
+pub fn proposal_addresses(
+    treasury: &Pubkey,
+    agent: &Pubkey,
+    counter: &ProposalCounter,
+) -> Vec<Pubkey> {
+    (0..counter.next_sequence)
+        .map(|sequence| proposal_address(treasury, agent, sequence).0)
+        .collect()
+}
+
+/// Vote record address for a voter on a proposal
+pub fn vote_record_address(proposal: &Pubkey, voter: &Pubkey) -> (Pubkey, u8) {
+    Pubkey::find_program_address(
+        &[b"vote", proposal.as_ref(), voter.as_ref()],
+        &treasury::ID,
+    )
+}
+
+/// PDA that owns every vault token account and share mint of a treasury
+pub fn vault_authority_address(treasury: &Pubkey) -> (Pubkey, u8) {
+    Pubkey::find_program_address(
+        &[b"vault_authority", treasury.as_ref()],
+        &treasury::ID,
+    )
+}
+
+/// Per-mint asset record address
+pub fn vault_asset_address(treasury: &Pubkey, mint: &Pubkey) -> (Pubkey, u8) {
+    Pubkey::find_program_address(
+        &[b"vault_asset", treasury.as_ref(), mint.as_ref()],
+        &treasury::ID,
+    )
+}
+
+/// Per-mint vault token account address
+pub fn vault_address(treasury: &Pubkey, mint: &Pubkey) -> (Pubkey, u8) {
+    Pubkey::find_program_address(
+        &[b"vault", treasury.as_ref(), mint.as_ref()],
+        &treasury::ID,
+    )
+}
+
+/// Per-mint share mint address
+pub fn share_mint_address(treasury: &Pubkey, mint: &Pubkey) -> (Pubkey, u8) {
+    Pubkey::find_program_address(
+        &[b"share_mint", treasury.as_ref(), mint.as_ref()],
+        &treasury::ID,
+    )
+}
+
+/// Treasury council address
+pub fn council_address(treasury: &Pubkey) -> (Pubkey, u8) {
+    Pubkey::find_program_address(&[b"council", treasury.as_ref()], &treasury::ID)
+}
+
+/// Pending timelocked `TreasuryParams` change; at most one exists per treasury
+pub fn params_change_address(treasury: &Pubkey) -> (Pubkey, u8) {
+    Pubkey::find_program_address(&[b"params_change", treasury.as_ref()], &treasury::ID)
+}
+
+/// Council approval record for executing a proposal
+pub fn execution_approval_address(council: &Pubkey, proposal: &Pubkey) -> (Pubkey, u8) {
+    Pubkey::find_program_address(
+        &[b"execution_approval", council.as_ref(), proposal.as_ref()],
+        &treasury::ID,
+    )
+}
+
+/// Immutable snapshot written when `epoch` was closed by `advance_epoch`
+pub fn epoch_snapshot_address(treasury: &Pubkey, epoch: u64) -> (Pubkey, u8) {
+    Pubkey::find_program_address(
+        &[b"epoch_snapshot", treasury.as_ref(), &epoch.to_le_bytes()],
+        &treasury::ID,
+    )
+}
+
+/// PDA the treasury signs with when adjusting agent reputation in agent_registry
+/// The registry must whitelist this address with `add_reputation_adjuster`
+pub fn reputation_authority_address(treasury: &Pubkey) -> (Pubkey, u8) {
+    Pubkey::find_program_address(
+        &[b"reputation_authority", treasury.as_ref()],
+        &treasury::ID,
+    )
+}
+
+/// Adapter executing decisions for `target_protocol` on `mint`
+pub fn strategy_adapter_address(
+    treasury: &Pubkey,
+    mint: &Pubkey,
+    target_protocol: &str,
+) -> (Pubkey, u8) {
+    Pubkey::find_program_address(
+        &[
+            b"strategy_adapter",
+            treasury.as_ref(),
+            mint.as_ref(),
+            target_protocol.as_bytes(),
+        ],
+        &treasury::ID,
+    )
+}
+
+/// Position ledger entry for `target_protocol` on `mint`, created with its strategy adapter
+pub fn position_address(treasury: &Pubkey, mint: &Pubkey, target_protocol: &str) -> (Pubkey, u8) {
+    Pubkey::find_program_address(
+        &[
+            b"position",
+            treasury.as_ref(),
+            mint.as_ref(),
+            target_protocol.as_bytes(),
+        ],
+        &treasury::ID,
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn next_proposal_address_follows_counter() {
+        let treasury = Pubkey::new_unique();
+        let agent = Pubkey::new_unique();
+        let counter = ProposalCounter {
+            treasury,
+            agent,
+            next_sequence: 2,
+            bump: 0,
+        };
+
+        assert_eq!(
+            next_proposal_address(&treasury, &agent, None),
+            proposal_address(&treasury, &agent, 0)
+        );
+        assert_eq!(
+            next_proposal_address(&treasury, &agent, Some(&counter)),
+            proposal_address(&treasury, &agent, 2)
+        );
+        assert_eq!(proposal_addresses(&treasury, &agent, &counter).len(), 2);
+    }
+
+    #[test]
+    fn proposal_addresses_are_unique_per_sequence() {
+        let treasury = Pubkey::new_unique();
+        let agent = Pubkey::new_unique();
+
+        assert_ne!(
+            proposal_address(&treasury, &agent, 0).0,
+            proposal_address(&treasury, &agent, 1).0
+        );
+    }
+}
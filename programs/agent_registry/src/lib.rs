@@ -5,17 +5,32 @@ use anchor_lang::prelude::*;
 
 declare_id!("9vqS7h8TMjuu7cuzdh6NLzG4JikDi6r1saaxZdTtByYJ");
 
+// Pause flags, one bit per instruction category
+pub const PAUSE_REGISTRATION: u16 = 1 << 0;
+pub const PAUSE_TASK_CREATION: u16 = 1 << 1;
+pub const PAUSE_BIDDING: u16 = 1 << 2;
+pub const PAUSE_COMPLETION: u16 = 1 << 3;
+pub const PAUSE_ALL: u16 = PAUSE_REGISTRATION
+    | PAUSE_TASK_CREATION
+    | PAUSE_BIDDING
+    | PAUSE_COMPLETION;
+
 #[program]
 pub mod agent_registry {
     use super::*;
 
     /// Initialize the agent registry
     pub fn initialize(ctx: Context<Initialize>, params: RegistryParams) -> Result<()> {
+        params.validate()?;
+        
         let registry = &mut ctx.accounts.registry;
         registry.authority = ctx.accounts.authority.key();
+        registry.pending_authority = None;
         registry.params = params;
         registry.total_agents = 0;
         registry.total_tasks_completed = 0;
+        registry.guardian = Pubkey::default();
+        registry.paused = 0;
         
         emit!(RegistryInitialized {
             registry: registry.key(),
@@ -25,6 +40,147 @@ pub mod agent_registry {
         Ok(())
     }
 
+    /// Propose a new authority; takes effect only once the new key accepts
+    pub fn propose_authority_transfer(
+        ctx: Context<ProposeAuthorityTransfer>,
+        new_authority: Pubkey,
+    ) -> Result<()> {
+        let registry = &mut ctx.accounts.registry;
+        
+        require!(
+            ctx.accounts.authority.key() == registry.authority,
+            RegistryError::Unauthorized
+        );
+        
+        registry.pending_authority = Some(new_authority);
+        
+        emit!(AuthorityTransferProposed {
+            registry: registry.key(),
+            current_authority: registry.authority,
+            pending_authority: new_authority,
+        });
+        
+        Ok(())
+    }
+
+    /// Pending authority accepts the transfer and becomes the authority
+    pub fn accept_authority_transfer(ctx: Context<AcceptAuthorityTransfer>) -> Result<()> {
+        let registry = &mut ctx.accounts.registry;
+        
+        require!(
+            registry.pending_authority == Some(ctx.accounts.new_authority.key()),
+            RegistryError::NotPendingAuthority
+        );
+        
+        let old_authority = registry.authority;
+        registry.authority = ctx.accounts.new_authority.key();
+        registry.pending_authority = None;
+        
+        emit!(AuthorityTransferAccepted {
+            registry: registry.key(),
+            old_authority,
+            new_authority: registry.authority,
+        });
+        
+        Ok(())
+    }
+
+    /// Current authority withdraws a pending transfer
+    pub fn cancel_authority_transfer(ctx: Context<CancelAuthorityTransfer>) -> Result<()> {
+        let registry = &mut ctx.accounts.registry;
+        
+        require!(
+            ctx.accounts.authority.key() == registry.authority,
+            RegistryError::Unauthorized
+        );
+        let cancelled_authority = registry
+            .pending_authority
+            .take()
+            .ok_or(RegistryError::NoPendingAuthority)?;
+        
+        emit!(AuthorityTransferCancelled {
+            registry: registry.key(),
+            authority: registry.authority,
+            cancelled_authority,
+        });
+        
+        Ok(())
+    }
+
+    /// Set the guardian key that may pause, but never unpause, the registry
+    pub fn set_guardian(ctx: Context<SetGuardian>, guardian: Pubkey) -> Result<()> {
+        let registry = &mut ctx.accounts.registry;
+        
+        require!(
+            ctx.accounts.authority.key() == registry.authority,
+            RegistryError::Unauthorized
+        );
+        
+        let old_guardian = registry.guardian;
+        registry.guardian = guardian;
+        
+        emit!(GuardianUpdated {
+            registry: registry.key(),
+            old_guardian,
+            new_guardian: guardian,
+        });
+        
+        Ok(())
+    }
+
+    /// Replace the registry parameters
+    pub fn update_params(ctx: Context<UpdateParams>, params: RegistryParams) -> Result<()> {
+        let registry = &mut ctx.accounts.registry;
+        
+        require!(
+            ctx.accounts.authority.key() == registry.authority,
+            RegistryError::Unauthorized
+        );
+        params.validate()?;
+        
+        let old_params = registry.params.clone();
+        registry.params = params.clone();
+        
+        emit!(ParamsUpdated {
+            registry: registry.key(),
+            old_params,
+            new_params: params,
+        });
+        
+        Ok(())
+    }
+
+    /// Update the pause flags
+    /// SECURITY: Authority may set any flags; guardian may only add flags
+    pub fn set_paused(ctx: Context<SetPaused>, flags: u16) -> Result<()> {
+        let registry = &mut ctx.accounts.registry;
+        let signer = ctx.accounts.signer.key();
+        
+        require!(flags & !PAUSE_ALL == 0, RegistryError::InvalidPauseFlags);
+        if signer != registry.authority {
+            require!(
+                registry.guardian != Pubkey::default() && signer == registry.guardian,
+                RegistryError::UnauthorizedPauser
+            );
+            require!(
+                flags & registry.paused == registry.paused,
+                RegistryError::GuardianCannotUnpause
+            );
+        }
+        
+        let old_flags = registry.paused;
+        registry.paused = flags;
+        
+        emit!(PauseUpdated {
+            registry: registry.key(),
+            old_flags,
+            new_flags: flags,
+            updated_by: signer,
+        });
+        
+        Ok(())
+    }
+
     /// Register a new agent with specialization
     pub fn register_agent(
         ctx: Context<RegisterAgent>,
@@ -34,7 +190,13 @@ pub mod agent_registry {
         let registry = &mut ctx.accounts.registry;
         let agent = &mut ctx.accounts.agent;
         
+        require!(
+            !registry.is_paused(PAUSE_REGISTRATION),
+            RegistryError::Paused
+        );
+        
         agent.owner = ctx.accounts.owner.key();
+        agent.registry = registry.key();
         agent.agent_type = specialization;
         agent.capabilities = capabilities;
         agent.reputation_score = 500; // Start at neutral 500/1000
@@ -69,6 +231,11 @@ pub mod agent_registry {
         let task = &mut ctx.accounts.task;
         let current_time = Clock::get()?.unix_timestamp;
         
+        require!(
+            !ctx.accounts.registry.is_paused(PAUSE_TASK_CREATION),
+            RegistryError::Paused
+        );
+        
         // SECURITY: Validate deadline is in the future
         require!(
             deadline > current_time,
@@ -82,6 +249,7 @@ pub mod agent_registry {
         );
         
         task.creator = ctx.accounts.creator.key();
+        task.registry = ctx.accounts.registry.key();
         task.task_type = task_type;
         task.description_hash = description_hash;
         task.reward = reward;
@@ -111,6 +279,10 @@ pub mod agent_registry {
         let task = &mut ctx.accounts.task;
         let agent = &ctx.accounts.agent;
         
+        require!(
+            !ctx.accounts.registry.is_paused(PAUSE_BIDDING),
+            RegistryError::Paused
+        );
         require!(
             matches!(task.status, TaskStatus::Open),
             RegistryError::TaskNotOpen
@@ -155,6 +327,10 @@ pub mod agent_registry {
         let task = &mut ctx.accounts.task;
         let creator = &ctx.accounts.creator;
         
+        require!(
+            !ctx.accounts.registry.is_paused(PAUSE_BIDDING),
+            RegistryError::Paused
+        );
         require!(
             task.creator == creator.key(),
             RegistryError::Unauthorized
@@ -192,6 +368,10 @@ pub mod agent_registry {
         let agent = &mut ctx.accounts.agent;
         let registry = &mut ctx.accounts.registry;
         
+        require!(
+            !registry.is_paused(PAUSE_COMPLETION),
+            RegistryError::Paused
+        );
         require!(
             matches!(task.status, TaskStatus::Assigned),
             RegistryError::TaskNotAssigned
@@ -241,6 +421,69 @@ pub mod agent_registry {
         Ok(())
     }
 
+    /// Whitelist a program PDA that may adjust agent reputation via CPI
+    /// SECURITY: Only registry authority can whitelist adjusters
+    pub fn add_reputation_adjuster(
+        ctx: Context<AddReputationAdjuster>,
+        adjuster: Pubkey,
+    ) -> Result<()> {
+        let registry = &ctx.accounts.registry;
+        let record = &mut ctx.accounts.adjuster_record;
+        
+        require!(
+            ctx.accounts.authority.key() == registry.authority,
+            RegistryError::Unauthorized
+        );
+        // Adjusters are program PDAs; a wallet key could sign adjustments directly
+        require!(!adjuster.is_on_curve(), RegistryError::AdjusterNotPda);
+        
+        record.registry = registry.key();
+        record.adjuster = adjuster;
+        record.added_at = Clock::get()?.unix_timestamp;
+        record.bump = ctx.bumps.adjuster_record;
+        
+        emit!(ReputationAdjusterAdded {
+            registry: registry.key(),
+            adjuster,
+        });
+        
+        Ok(())
+    }
+
+    /// Remove a whitelisted reputation adjuster
+    pub fn remove_reputation_adjuster(ctx: Context<RemoveReputationAdjuster>) -> Result<()> {
+        require!(
+            ctx.accounts.authority.key() == ctx.accounts.registry.authority,
+            RegistryError::Unauthorized
+        );
+        
+        emit!(ReputationAdjusterRemoved {
+            registry: ctx.accounts.registry.key(),
+            adjuster: ctx.accounts.adjuster_record.adjuster,
+        });
+        
+        Ok(())
+    }
+
+    /// Adjust an agent's reputation, clamped to 0-1000
+    /// SECURITY: Signer must be a whitelisted program PDA, reachable only via CPI
+    pub fn adjust_reputation(ctx: Context<AdjustReputation>, delta: i16) -> Result<()> {
+        let agent = &mut ctx.accounts.agent;
+        
+        let old_reputation = agent.reputation_score;
+        agent.reputation_score = (old_reputation as i32 + delta as i32).clamp(0, 1000) as u16;
+        
+        emit!(ReputationAdjusted {
+            agent: agent.key(),
+            adjuster: ctx.accounts.adjuster.key(),
+            delta,
+            old_reputation,
+            new_reputation: agent.reputation_score,
+        });
+        
+        Ok(())
+    }
+
     /// Update agent status (active, inactive, suspended)
     pub fn update_agent_status(
         ctx: Context<UpdateAgentStatus>,
@@ -248,6 +491,11 @@ pub mod agent_registry {
     ) -> Result<()> {
         let agent = &mut ctx.accounts.agent;
         
+        // Status changes are part of the registration lifecycle
+        require!(
+            !ctx.accounts.registry.is_paused(PAUSE_REGISTRATION),
+            RegistryError::Paused
+        );
         require!(
             ctx.accounts.owner.key() == agent.owner,
             RegistryError::Unauthorized
@@ -287,6 +535,27 @@ pub struct Initialize<'info> {
     pub system_program: Program<'info, System>,
 }
 
+#[derive(Accounts)]
+pub struct ProposeAuthorityTransfer<'info> {
+    #[account(mut)]
+    pub registry: Account<'info, Registry>,
+    pub authority: Signer<'info>,
+}
+
+#[derive(Accounts)]
+pub struct AcceptAuthorityTransfer<'info> {
+    #[account(mut)]
+    pub registry: Account<'info, Registry>,
+    pub new_authority: Signer<'info>,
+}
+
+#[derive(Accounts)]
+pub struct CancelAuthorityTransfer<'info> {
+    #[account(mut)]
+    pub registry: Account<'info, Registry>,
+    pub authority: Signer<'info>,
+}
+
 #[derive(Accounts)]
 pub struct RegisterAgent<'info> {
     #[account(mut)]
@@ -302,8 +571,30 @@ pub struct RegisterAgent<'info> {
     pub system_program: Program<'info, System>,
 }
 
+#[derive(Accounts)]
+pub struct SetGuardian<'info> {
+    #[account(mut)]
+    pub registry: Account<'info, Registry>,
+    pub authority: Signer<'info>,
+}
+
+#[derive(Accounts)]
+pub struct UpdateParams<'info> {
+    #[account(mut)]
+    pub registry: Account<'info, Registry>,
+    pub authority: Signer<'info>,
+}
+
+#[derive(Accounts)]
+pub struct SetPaused<'info> {
+    #[account(mut)]
+    pub registry: Account<'info, Registry>,
+    pub signer: Signer<'info>,
+}
+
 #[derive(Accounts)]
 pub struct CreateTask<'info> {
+    pub registry: Account<'info, Registry>,
     #[account(
         init,
         payer = creator,
@@ -317,32 +608,83 @@ pub struct CreateTask<'info> {
 
 #[derive(Accounts)]
 pub struct BidOnTask<'info> {
-    #[account(mut)]
+    pub registry: Account<'info, Registry>,
+    #[account(mut, has_one = registry)]
     pub task: Account<'info, Task>,
+    #[account(has_one = registry)]
     pub agent: Account<'info, Agent>,
     pub bidder: Signer<'info>,
 }
 
 #[derive(Accounts)]
 pub struct AcceptBid<'info> {
-    #[account(mut)]
+    pub registry: Account<'info, Registry>,
+    #[account(mut, has_one = registry)]
     pub task: Account<'info, Task>,
     pub creator: Signer<'info>,
 }
 
 #[derive(Accounts)]
 pub struct CompleteTask<'info> {
-    #[account(mut)]
+    #[account(mut, has_one = registry)]
     pub task: Account<'info, Task>,
-    #[account(mut)]
+    #[account(mut, has_one = registry)]
     pub agent: Account<'info, Agent>,
     #[account(mut)]
     pub registry: Account<'info, Registry>,
     pub completer: Signer<'info>,
 }
 
+#[derive(Accounts)]
+#[instruction(adjuster: Pubkey)]
+pub struct AddReputationAdjuster<'info> {
+    pub registry: Account<'info, Registry>,
+    #[account(
+        init,
+        payer = authority,
+        space = ReputationAdjuster::SIZE,
+        seeds = [b"reputation_adjuster", registry.key().as_ref(), adjuster.as_ref()],
+        bump
+    )]
+    pub adjuster_record: Account<'info, ReputationAdjuster>,
+    #[account(mut)]
+    pub authority: Signer<'info>,
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+pub struct RemoveReputationAdjuster<'info> {
+    pub registry: Account<'info, Registry>,
+    #[account(
+        mut,
+        close = authority,
+        has_one = registry,
+        seeds = [b"reputation_adjuster", registry.key().as_ref(), adjuster_record.adjuster.as_ref()],
+        bump = adjuster_record.bump
+    )]
+    pub adjuster_record: Account<'info, ReputationAdjuster>,
+    #[account(mut)]
+    pub authority: Signer<'info>,
+}
+
+#[derive(Accounts)]
+pub struct AdjustReputation<'info> {
+    pub registry: Account<'info, Registry>,
+    #[account(mut, has_one = registry)]
+    pub agent: Account<'info, Agent>,
+    #[account(
+        has_one = registry,
+        seeds = [b"reputation_adjuster", registry.key().as_ref(), adjuster.key().as_ref()],
+        bump = adjuster_record.bump
+    )]
+    pub adjuster_record: Account<'info, ReputationAdjuster>,
+    pub adjuster: Signer<'info>,
+}
+
 #[derive(Accounts)]
 pub struct UpdateAgentStatus<'info> {
+    #[account(address = agent.registry)]
+    pub registry: Account<'info, Registry>,
     #[account(mut)]
     pub agent: Account<'info, Agent>,
     pub owner: Signer<'info>,
@@ -356,13 +698,20 @@ pub struct RecommendAgents<'info> {
 #[account]
 pub struct Registry {
     pub authority: Pubkey,
+    pub pending_authority: Option<Pubkey>,
     pub params: RegistryParams,
     pub total_agents: u64,
     pub total_tasks_completed: u64,
+    pub guardian: Pubkey, // May pause but not unpause
+    pub paused: u16,      // Bitflags of PAUSE_* categories
 }
 
 impl Registry {
-    pub const SIZE: usize = 8 + 32 + RegistryParams::SIZE + 8 + 8;
+    pub const SIZE: usize = 8 + 32 + (1 + 32) + RegistryParams::SIZE + 8 + 8 + 32 + 2;
+
+    pub fn is_paused(&self, flag: u16) -> bool {
+        self.paused & flag != 0
+    }
 }
 
 #[derive(AnchorSerialize, AnchorDeserialize, Clone, Debug)]
@@ -374,11 +723,32 @@ pub struct RegistryParams {
 
 impl RegistryParams {
     pub const SIZE: usize = 8 + 2 + 2;
+
+    pub fn validate(&self) -> Result<()> {
+        require!(
+            self.reputation_decay_rate <= 10_000 && self.task_timeout_slash_bps <= 10_000,
+            RegistryError::InvalidBps
+        );
+        Ok(())
+    }
+}
+
+#[account]
+pub struct ReputationAdjuster {
+    pub registry: Pubkey,
+    pub adjuster: Pubkey, // Program PDA allowed to sign adjust_reputation
+    pub added_at: i64,
+    pub bump: u8,
+}
+
+impl ReputationAdjuster {
+    pub const SIZE: usize = 8 + 32 + 32 + 8 + 1;
 }
 
 #[account]
 pub struct Agent {
     pub owner: Pubkey,
+    pub registry: Pubkey,
     pub agent_type: AgentType,
     pub capabilities: Vec<String>,
     pub reputation_score: u16, // 0-1000
@@ -393,6 +763,7 @@ pub struct Agent {
 impl Agent {
     pub const SIZE: usize = 8 + // discriminator
         32 + // owner
+        32 + // registry
         AgentType::SIZE +
         4 + (10 * 40) + // capabilities vec (max 10 capabilities, 40 bytes each)
         2 + // reputation_score
@@ -427,6 +798,7 @@ pub enum AgentStatus {
 #[account]
 pub struct Task {
     pub creator: Pubkey,
+    pub registry: Pubkey,
     pub task_type: TaskType,
     pub description_hash: [u8; 32],
     pub reward: u64,
@@ -444,6 +816,7 @@ pub struct Task {
 impl Task {
     pub const SIZE: usize = 8 + // discriminator
         32 + // creator
+        32 + // registry
         TaskType::SIZE +
         32 + // description_hash
         8 + // reward
@@ -524,6 +897,22 @@ pub enum RegistryError {
     InvalidDeadline,
     #[msg("Deadline too far in the future")]
     DeadlineTooFar,
+    #[msg("Signer is not the pending authority")]
+    NotPendingAuthority,
+    #[msg("No authority transfer is pending")]
+    NoPendingAuthority,
+    #[msg("Instruction category is paused")]
+    Paused,
+    #[msg("Unknown pause flags")]
+    InvalidPauseFlags,
+    #[msg("Signer may not change pause flags")]
+    UnauthorizedPauser,
+    #[msg("Guardian can only pause")]
+    GuardianCannotUnpause,
+    #[msg("Basis point value exceeds 10,000")]
+    InvalidBps,
+    #[msg("Reputation adjuster must be a program-derived address")]
+    AdjusterNotPda,
 }
 
 // Events
@@ -533,6 +922,28 @@ pub struct RegistryInitialized {
     pub authority: Pubkey,
 }
 
+#[event]
+pub struct GuardianUpdated {
+    pub registry: Pubkey,
+    pub old_guardian: Pubkey,
+    pub new_guardian: Pubkey,
+}
+
+#[event]
+pub struct PauseUpdated {
+    pub registry: Pubkey,
+    pub old_flags: u16,
+    pub new_flags: u16,
+    pub updated_by: Pubkey,
+}
+
+#[event]
+pub struct ParamsUpdated {
+    pub registry: Pubkey,
+    pub old_params: RegistryParams,
+    pub new_params: RegistryParams,
+}
+
 #[event]
 pub struct AgentRegistered {
     pub agent: Pubkey,
@@ -571,8 +982,50 @@ pub struct TaskCompleted {
     pub new_reputation: u16,
 }
 
+#[event]
+pub struct ReputationAdjusterAdded {
+    pub registry: Pubkey,
+    pub adjuster: Pubkey,
+}
+
+#[event]
+pub struct ReputationAdjusterRemoved {
+    pub registry: Pubkey,
+    pub adjuster: Pubkey,
+}
+
+#[event]
+pub struct ReputationAdjusted {
+    pub agent: Pubkey,
+    pub adjuster: Pubkey,
+    pub delta: i16,
+    pub old_reputation: u16,
+    pub new_reputation: u16,
+}
+
 #[event]
 pub struct AgentStatusUpdated {
     pub agent: Pubkey,
     pub new_status: AgentStatus,
 }
+
+#[event]
+pub struct AuthorityTransferProposed {
+    pub registry: Pubkey,
+    pub current_authority: Pubkey,
+    pub pending_authority: Pubkey,
+}
+
+#[event]
+pub struct AuthorityTransferAccepted {
+    pub registry: Pubkey,
+    pub old_authority: Pubkey,
+    pub new_authority: Pubkey,
+}
+
+#[event]
+pub struct AuthorityTransferCancelled {
+    pub registry: Pubkey,
+    pub authority: Pubkey,
+    pub cancelled_authority: Pubkey,
+}
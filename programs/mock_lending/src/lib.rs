@@ -0,0 +1,343 @@
+use anchor_lang::prelude::*;
+use anchor_spl::token::{self, Mint, Token, TokenAccount, Transfer};
+
+// Aethernaut Mock Lending
+// Simple-interest lending market; receipts grow at a fixed APR the treasury realizes on withdraw
+
+declare_id!("57rDSCKGPiukPoo4rSx3vrnQRzoEtZxWkhRXL2FTEARQ");
+
+pub const SECONDS_PER_YEAR: u64 = 31_536_000;
+pub const BPS_DENOMINATOR: u64 = 10_000;
+
+#[program]
+pub mod mock_lending {
+    use super::*;
+
+    /// Create a lending market for a mint with a fixed simple-interest rate
+    pub fn initialize_market(ctx: Context<InitializeMarket>, rate_bps: u16) -> Result<()> {
+        let market = &mut ctx.accounts.market;
+        market.authority = ctx.accounts.authority.key();
+        market.mint = ctx.accounts.mint.key();
+        market.reserve = ctx.accounts.reserve.key();
+        market.rate_bps = rate_bps;
+        market.total_deposits = 0;
+        market.bump = ctx.bumps.market;
+
+        emit!(MarketInitialized {
+            market: market.key(),
+            mint: market.mint,
+            rate_bps,
+        });
+
+        Ok(())
+    }
+
+    /// Change the annual rate; existing receipts accrue at the new rate from their next touch
+    pub fn set_rate(ctx: Context<SetRate>, rate_bps: u16) -> Result<()> {
+        let market = &mut ctx.accounts.market;
+
+        require!(
+            ctx.accounts.authority.key() == market.authority,
+            LendingError::Unauthorized
+        );
+
+        market.rate_bps = rate_bps;
+
+        emit!(RateUpdated {
+            market: market.key(),
+            rate_bps,
+        });
+
+        Ok(())
+    }
+
+    /// Top up the reserve so accrued interest can be paid out
+    pub fn fund_reserve(ctx: Context<FundReserve>, amount: u64) -> Result<()> {
+        require!(amount > 0, LendingError::ZeroAmount);
+
+        let cpi_accounts = Transfer {
+            from: ctx.accounts.source.to_account_info(),
+            to: ctx.accounts.reserve.to_account_info(),
+            authority: ctx.accounts.funder.to_account_info(),
+        };
+        let cpi_ctx = CpiContext::new(ctx.accounts.token_program.to_account_info(), cpi_accounts);
+
+        token::transfer(cpi_ctx, amount)
+    }
+
+    /// Open a deposit receipt for `owner`, typically a treasury vault authority PDA
+    pub fn open_receipt(ctx: Context<OpenReceipt>, owner: Pubkey) -> Result<()> {
+        let receipt = &mut ctx.accounts.receipt;
+        receipt.market = ctx.accounts.market.key();
+        receipt.owner = owner;
+        receipt.balance = 0;
+        receipt.last_accrued = Clock::get()?.unix_timestamp;
+        receipt.bump = ctx.bumps.receipt;
+
+        Ok(())
+    }
+
+    /// Strategy adapter deposit: pull `amount` from the owner's token account
+    pub fn deposit(ctx: Context<Deposit>, amount: u64) -> Result<()> {
+        require!(amount > 0, LendingError::ZeroAmount);
+
+        let now = Clock::get()?.unix_timestamp;
+        let market = &mut ctx.accounts.market;
+        let receipt = &mut ctx.accounts.receipt;
+        receipt.accrue(market.rate_bps, now)?;
+
+        let cpi_accounts = Transfer {
+            from: ctx.accounts.source.to_account_info(),
+            to: ctx.accounts.reserve.to_account_info(),
+            authority: ctx.accounts.owner.to_account_info(),
+        };
+        let cpi_ctx = CpiContext::new(ctx.accounts.token_program.to_account_info(), cpi_accounts);
+        token::transfer(cpi_ctx, amount)?;
+
+        receipt.balance = receipt.balance.checked_add(amount).unwrap();
+        market.total_deposits = market.total_deposits.checked_add(amount).unwrap();
+
+        emit!(Deposited {
+            market: market.key(),
+            owner: receipt.owner,
+            amount,
+            new_balance: receipt.balance,
+        });
+
+        Ok(())
+    }
+
+    /// Strategy adapter withdraw: return `amount` of principal plus accrued interest
+    pub fn withdraw(ctx: Context<Withdraw>, amount: u64) -> Result<()> {
+        require!(amount > 0, LendingError::ZeroAmount);
+
+        let now = Clock::get()?.unix_timestamp;
+        let market = &mut ctx.accounts.market;
+        let receipt = &mut ctx.accounts.receipt;
+        receipt.accrue(market.rate_bps, now)?;
+        require!(amount <= receipt.balance, LendingError::InsufficientBalance);
+
+        let mint_key = market.mint;
+        let signer_seeds: &[&[&[u8]]] = &[&[b"market", mint_key.as_ref(), &[market.bump]]];
+        let cpi_accounts = Transfer {
+            from: ctx.accounts.reserve.to_account_info(),
+            to: ctx.accounts.destination.to_account_info(),
+            authority: market.to_account_info(),
+        };
+        let cpi_ctx = CpiContext::new_with_signer(
+            ctx.accounts.token_program.to_account_info(),
+            cpi_accounts,
+            signer_seeds,
+        );
+        token::transfer(cpi_ctx, amount)?;
+
+        receipt.balance = receipt.balance.checked_sub(amount).unwrap();
+        market.total_deposits = market.total_deposits.saturating_sub(amount);
+
+        emit!(Withdrawn {
+            market: market.key(),
+            owner: receipt.owner,
+            amount,
+            new_balance: receipt.balance,
+        });
+
+        Ok(())
+    }
+}
+
+#[derive(Accounts)]
+pub struct InitializeMarket<'info> {
+    #[account(
+        init,
+        payer = authority,
+        space = LendingMarket::SIZE,
+        seeds = [b"market", mint.key().as_ref()],
+        bump
+    )]
+    pub market: Account<'info, LendingMarket>,
+    pub mint: Account<'info, Mint>,
+    #[account(
+        init,
+        payer = authority,
+        token::mint = mint,
+        token::authority = market,
+        seeds = [b"reserve", market.key().as_ref()],
+        bump
+    )]
+    pub reserve: Account<'info, TokenAccount>,
+    #[account(mut)]
+    pub authority: Signer<'info>,
+    pub token_program: Program<'info, Token>,
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+pub struct SetRate<'info> {
+    #[account(mut)]
+    pub market: Account<'info, LendingMarket>,
+    pub authority: Signer<'info>,
+}
+
+#[derive(Accounts)]
+pub struct FundReserve<'info> {
+    #[account(has_one = reserve)]
+    pub market: Account<'info, LendingMarket>,
+    #[account(mut)]
+    pub reserve: Account<'info, TokenAccount>,
+    #[account(mut)]
+    pub source: Account<'info, TokenAccount>,
+    pub funder: Signer<'info>,
+    pub token_program: Program<'info, Token>,
+}
+
+#[derive(Accounts)]
+#[instruction(owner: Pubkey)]
+pub struct OpenReceipt<'info> {
+    pub market: Account<'info, LendingMarket>,
+    #[account(
+        init,
+        payer = payer,
+        space = Receipt::SIZE,
+        seeds = [b"receipt", market.key().as_ref(), owner.as_ref()],
+        bump
+    )]
+    pub receipt: Account<'info, Receipt>,
+    #[account(mut)]
+    pub payer: Signer<'info>,
+    pub system_program: Program<'info, System>,
+}
+
+// Adapter layout after (owner, token account, token program): market, reserve, receipt
+// The receipt is keyed by the signing owner, so each treasury needs one opened first
+#[derive(Accounts)]
+pub struct Deposit<'info> {
+    pub owner: Signer<'info>,
+    #[account(mut, token::mint = market.mint)]
+    pub source: Account<'info, TokenAccount>,
+    pub token_program: Program<'info, Token>,
+    #[account(mut, has_one = reserve)]
+    pub market: Account<'info, LendingMarket>,
+    #[account(mut)]
+    pub reserve: Account<'info, TokenAccount>,
+    #[account(
+        mut,
+        seeds = [b"receipt", market.key().as_ref(), owner.key().as_ref()],
+        bump = receipt.bump
+    )]
+    pub receipt: Account<'info, Receipt>,
+}
+
+#[derive(Accounts)]
+pub struct Withdraw<'info> {
+    pub owner: Signer<'info>,
+    #[account(mut, token::mint = market.mint)]
+    pub destination: Account<'info, TokenAccount>,
+    pub token_program: Program<'info, Token>,
+    #[account(mut, has_one = reserve)]
+    pub market: Account<'info, LendingMarket>,
+    #[account(mut)]
+    pub reserve: Account<'info, TokenAccount>,
+    #[account(
+        mut,
+        seeds = [b"receipt", market.key().as_ref(), owner.key().as_ref()],
+        bump = receipt.bump
+    )]
+    pub receipt: Account<'info, Receipt>,
+}
+
+#[account]
+pub struct LendingMarket {
+    pub authority: Pubkey,
+    pub mint: Pubkey,
+    pub reserve: Pubkey,
+    pub rate_bps: u16,       // Annual simple interest
+    pub total_deposits: u64, // Principal plus interest credited to receipts
+    pub bump: u8,
+}
+
+impl LendingMarket {
+    pub const SIZE: usize = 8 + // discriminator
+        32 + // authority
+        32 + // mint
+        32 + // reserve
+        2 + // rate_bps
+        8 + // total_deposits
+        1; // bump
+}
+
+#[account]
+pub struct Receipt {
+    pub market: Pubkey,
+    pub owner: Pubkey,
+    pub balance: u64, // Principal plus interest accrued up to last_accrued
+    pub last_accrued: i64,
+    pub bump: u8,
+}
+
+impl Receipt {
+    pub const SIZE: usize = 8 + // discriminator
+        32 + // market
+        32 + // owner
+        8 + // balance
+        8 + // last_accrued
+        1; // bump
+
+    /// Credit simple interest for the time since the last touch
+    pub fn accrue(&mut self, rate_bps: u16, now: i64) -> Result<()> {
+        let elapsed = now.saturating_sub(self.last_accrued).max(0) as u128;
+        let interest = (self.balance as u128)
+            .checked_mul(rate_bps as u128)
+            .and_then(|v| v.checked_mul(elapsed))
+            .map(|v| v / (BPS_DENOMINATOR as u128 * SECONDS_PER_YEAR as u128))
+            .ok_or(LendingError::CalculationOverflow)?;
+
+        self.balance = self
+            .balance
+            .checked_add(u64::try_from(interest).map_err(|_| LendingError::CalculationOverflow)?)
+            .ok_or(LendingError::CalculationOverflow)?;
+        self.last_accrued = now;
+
+        Ok(())
+    }
+}
+
+#[error_code]
+pub enum LendingError {
+    #[msg("Unauthorized")]
+    Unauthorized,
+    #[msg("Amount must be greater than zero")]
+    ZeroAmount,
+    #[msg("Withdrawal exceeds receipt balance")]
+    InsufficientBalance,
+    #[msg("Calculation overflow")]
+    CalculationOverflow,
+}
+
+#[event]
+pub struct MarketInitialized {
+    pub market: Pubkey,
+    pub mint: Pubkey,
+    pub rate_bps: u16,
+}
+
+#[event]
+pub struct RateUpdated {
+    pub market: Pubkey,
+    pub rate_bps: u16,
+}
+
+#[event]
+pub struct Deposited {
+    pub market: Pubkey,
+    pub owner: Pubkey,
+    pub amount: u64,
+    pub new_balance: u64,
+}
+
+#[event]
+pub struct Withdrawn {
+    pub market: Pubkey,
+    pub owner: Pubkey,
+    pub amount: u64,
+    pub new_balance: u64,
+}
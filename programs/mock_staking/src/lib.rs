@@ -0,0 +1,385 @@
+use anchor_lang::prelude::*;
+use anchor_spl::token::{self, Mint, Token, TokenAccount, Transfer};
+
+// Aethernaut Mock Staking
+// Stake pool whose share exchange rate rises by a fixed reward every full period
+
+declare_id!("7UpT8NiJQ8NmxmM6YUTVZrJmVo9veVzQYBpz7agaNmHZ");
+
+pub const BPS_DENOMINATOR: u64 = 10_000;
+
+#[program]
+pub mod mock_staking {
+    use super::*;
+
+    /// Create a stake pool paying `reward_bps` of the staked total every `reward_period` seconds
+    pub fn initialize_pool(
+        ctx: Context<InitializePool>,
+        reward_bps: u16,
+        reward_period: i64,
+    ) -> Result<()> {
+        require!(reward_period > 0, StakingError::InvalidRewardPeriod);
+
+        let pool = &mut ctx.accounts.pool;
+        pool.authority = ctx.accounts.authority.key();
+        pool.mint = ctx.accounts.mint.key();
+        pool.reserve = ctx.accounts.reserve.key();
+        pool.reward_bps = reward_bps;
+        pool.reward_period = reward_period;
+        pool.total_staked = 0;
+        pool.total_shares = 0;
+        pool.last_update = Clock::get()?.unix_timestamp;
+        pool.bump = ctx.bumps.pool;
+
+        emit!(PoolInitialized {
+            pool: pool.key(),
+            mint: pool.mint,
+            reward_bps,
+            reward_period,
+        });
+
+        Ok(())
+    }
+
+    /// Change the reward schedule; rewards already earned are credited at the old rate
+    pub fn set_rewards(ctx: Context<SetRewards>, reward_bps: u16, reward_period: i64) -> Result<()> {
+        let pool = &mut ctx.accounts.pool;
+
+        require!(
+            ctx.accounts.authority.key() == pool.authority,
+            StakingError::Unauthorized
+        );
+        require!(reward_period > 0, StakingError::InvalidRewardPeriod);
+
+        pool.update(Clock::get()?.unix_timestamp)?;
+        pool.reward_bps = reward_bps;
+        pool.reward_period = reward_period;
+
+        emit!(RewardsUpdated {
+            pool: pool.key(),
+            reward_bps,
+            reward_period,
+        });
+
+        Ok(())
+    }
+
+    /// Top up the reserve so credited rewards can be paid out
+    pub fn fund_reserve(ctx: Context<FundReserve>, amount: u64) -> Result<()> {
+        require!(amount > 0, StakingError::ZeroAmount);
+
+        let cpi_accounts = Transfer {
+            from: ctx.accounts.source.to_account_info(),
+            to: ctx.accounts.reserve.to_account_info(),
+            authority: ctx.accounts.funder.to_account_info(),
+        };
+        let cpi_ctx = CpiContext::new(ctx.accounts.token_program.to_account_info(), cpi_accounts);
+
+        token::transfer(cpi_ctx, amount)
+    }
+
+    /// Open `owner`'s stake account so a later deposit can credit it shares
+    pub fn open_stake_account(ctx: Context<OpenStakeAccount>, owner: Pubkey) -> Result<()> {
+        let stake = &mut ctx.accounts.stake_account;
+        stake.pool = ctx.accounts.pool.key();
+        stake.owner = owner;
+        stake.shares = 0;
+        stake.bump = ctx.bumps.stake_account;
+
+        Ok(())
+    }
+
+    /// Strategy adapter deposit: stake `amount` for pool shares at the current exchange rate
+    pub fn deposit(ctx: Context<Deposit>, amount: u64) -> Result<()> {
+        require!(amount > 0, StakingError::ZeroAmount);
+
+        let pool = &mut ctx.accounts.pool;
+        let stake = &mut ctx.accounts.stake_account;
+        pool.update(Clock::get()?.unix_timestamp)?;
+
+        let shares = if pool.total_shares == 0 || pool.total_staked == 0 {
+            amount
+        } else {
+            mul_div(amount, pool.total_shares, pool.total_staked, false)?
+        };
+        require!(shares > 0, StakingError::ZeroAmount);
+
+        let cpi_accounts = Transfer {
+            from: ctx.accounts.source.to_account_info(),
+            to: ctx.accounts.reserve.to_account_info(),
+            authority: ctx.accounts.owner.to_account_info(),
+        };
+        let cpi_ctx = CpiContext::new(ctx.accounts.token_program.to_account_info(), cpi_accounts);
+        token::transfer(cpi_ctx, amount)?;
+
+        pool.total_staked = pool.total_staked.checked_add(amount).unwrap();
+        pool.total_shares = pool.total_shares.checked_add(shares).unwrap();
+        stake.shares = stake.shares.checked_add(shares).unwrap();
+
+        emit!(StakeUpdated {
+            pool: pool.key(),
+            owner: stake.owner,
+            amount,
+            shares: stake.shares,
+            total_staked: pool.total_staked,
+        });
+
+        Ok(())
+    }
+
+    /// Strategy adapter withdraw: unstake `amount` including rewards credited to the owner's shares
+    pub fn withdraw(ctx: Context<Withdraw>, amount: u64) -> Result<()> {
+        require!(amount > 0, StakingError::ZeroAmount);
+
+        let pool = &mut ctx.accounts.pool;
+        let stake = &mut ctx.accounts.stake_account;
+        pool.update(Clock::get()?.unix_timestamp)?;
+        require!(pool.total_staked > 0, StakingError::InsufficientBalance);
+
+        // Round shares burned up so stakers cannot extract dust at the pool's expense
+        let shares = mul_div(amount, pool.total_shares, pool.total_staked, true)?;
+        require!(shares <= stake.shares, StakingError::InsufficientBalance);
+
+        let mint_key = pool.mint;
+        let signer_seeds: &[&[&[u8]]] = &[&[b"stake_pool", mint_key.as_ref(), &[pool.bump]]];
+        let cpi_accounts = Transfer {
+            from: ctx.accounts.reserve.to_account_info(),
+            to: ctx.accounts.destination.to_account_info(),
+            authority: pool.to_account_info(),
+        };
+        let cpi_ctx = CpiContext::new_with_signer(
+            ctx.accounts.token_program.to_account_info(),
+            cpi_accounts,
+            signer_seeds,
+        );
+        token::transfer(cpi_ctx, amount)?;
+
+        pool.total_staked = pool.total_staked.saturating_sub(amount);
+        pool.total_shares -= shares;
+        stake.shares -= shares;
+
+        emit!(StakeUpdated {
+            pool: pool.key(),
+            owner: stake.owner,
+            amount,
+            shares: stake.shares,
+            total_staked: pool.total_staked,
+        });
+
+        Ok(())
+    }
+}
+
+/// `amount * numerator / denominator` in u128, rounded up when `round_up`
+pub fn mul_div(amount: u64, numerator: u64, denominator: u64, round_up: bool) -> Result<u64> {
+    let product = (amount as u128)
+        .checked_mul(numerator as u128)
+        .ok_or(StakingError::CalculationOverflow)?;
+    let result = if round_up {
+        product.div_ceil(denominator as u128)
+    } else {
+        product / denominator as u128
+    };
+    u64::try_from(result).map_err(|_| StakingError::CalculationOverflow.into())
+}
+
+#[derive(Accounts)]
+pub struct InitializePool<'info> {
+    #[account(
+        init,
+        payer = authority,
+        space = StakePool::SIZE,
+        seeds = [b"stake_pool", mint.key().as_ref()],
+        bump
+    )]
+    pub pool: Account<'info, StakePool>,
+    pub mint: Account<'info, Mint>,
+    #[account(
+        init,
+        payer = authority,
+        token::mint = mint,
+        token::authority = pool,
+        seeds = [b"stake_reserve", pool.key().as_ref()],
+        bump
+    )]
+    pub reserve: Account<'info, TokenAccount>,
+    #[account(mut)]
+    pub authority: Signer<'info>,
+    pub token_program: Program<'info, Token>,
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+pub struct SetRewards<'info> {
+    #[account(mut)]
+    pub pool: Account<'info, StakePool>,
+    pub authority: Signer<'info>,
+}
+
+#[derive(Accounts)]
+pub struct FundReserve<'info> {
+    #[account(has_one = reserve)]
+    pub pool: Account<'info, StakePool>,
+    #[account(mut)]
+    pub reserve: Account<'info, TokenAccount>,
+    #[account(mut)]
+    pub source: Account<'info, TokenAccount>,
+    pub funder: Signer<'info>,
+    pub token_program: Program<'info, Token>,
+}
+
+#[derive(Accounts)]
+#[instruction(owner: Pubkey)]
+pub struct OpenStakeAccount<'info> {
+    pub pool: Account<'info, StakePool>,
+    #[account(
+        init,
+        payer = payer,
+        space = StakeAccount::SIZE,
+        seeds = [b"stake", pool.key().as_ref(), owner.as_ref()],
+        bump
+    )]
+    pub stake_account: Account<'info, StakeAccount>,
+    #[account(mut)]
+    pub payer: Signer<'info>,
+    pub system_program: Program<'info, System>,
+}
+
+// Adapter layout after (owner, token account, token program): pool, reserve, stake account
+// Rewards are paid from the reserve, so it must be funded beyond the staked total
+#[derive(Accounts)]
+pub struct Deposit<'info> {
+    pub owner: Signer<'info>,
+    #[account(mut, token::mint = pool.mint)]
+    pub source: Account<'info, TokenAccount>,
+    pub token_program: Program<'info, Token>,
+    #[account(mut, has_one = reserve)]
+    pub pool: Account<'info, StakePool>,
+    #[account(mut)]
+    pub reserve: Account<'info, TokenAccount>,
+    #[account(
+        mut,
+        seeds = [b"stake", pool.key().as_ref(), owner.key().as_ref()],
+        bump = stake_account.bump
+    )]
+    pub stake_account: Account<'info, StakeAccount>,
+}
+
+#[derive(Accounts)]
+pub struct Withdraw<'info> {
+    pub owner: Signer<'info>,
+    #[account(mut, token::mint = pool.mint)]
+    pub destination: Account<'info, TokenAccount>,
+    pub token_program: Program<'info, Token>,
+    #[account(mut, has_one = reserve)]
+    pub pool: Account<'info, StakePool>,
+    #[account(mut)]
+    pub reserve: Account<'info, TokenAccount>,
+    #[account(
+        mut,
+        seeds = [b"stake", pool.key().as_ref(), owner.key().as_ref()],
+        bump = stake_account.bump
+    )]
+    pub stake_account: Account<'info, StakeAccount>,
+}
+
+#[account]
+pub struct StakePool {
+    pub authority: Pubkey,
+    pub mint: Pubkey,
+    pub reserve: Pubkey,
+    pub reward_bps: u16,    // Credited on total_staked once per reward_period
+    pub reward_period: i64, // Seconds
+    pub total_staked: u64,  // Stake plus rewards credited, backing total_shares
+    pub total_shares: u64,
+    pub last_update: i64,
+    pub bump: u8,
+}
+
+impl StakePool {
+    pub const SIZE: usize = 8 + // discriminator
+        32 + // authority
+        32 + // mint
+        32 + // reserve
+        2 + // reward_bps
+        8 + // reward_period
+        8 + // total_staked
+        8 + // total_shares
+        8 + // last_update
+        1; // bump
+
+    /// Credit rewards for every full period elapsed since the last update
+    pub fn update(&mut self, now: i64) -> Result<()> {
+        let periods = now.saturating_sub(self.last_update).max(0) / self.reward_period;
+        if periods == 0 {
+            return Ok(());
+        }
+
+        let rewards = (self.total_staked as u128)
+            .checked_mul(self.reward_bps as u128)
+            .and_then(|v| v.checked_mul(periods as u128))
+            .map(|v| v / BPS_DENOMINATOR as u128)
+            .ok_or(StakingError::CalculationOverflow)?;
+        self.total_staked = self
+            .total_staked
+            .checked_add(u64::try_from(rewards).map_err(|_| StakingError::CalculationOverflow)?)
+            .ok_or(StakingError::CalculationOverflow)?;
+        self.last_update += periods * self.reward_period;
+
+        Ok(())
+    }
+}
+
+#[account]
+pub struct StakeAccount {
+    pub pool: Pubkey,
+    pub owner: Pubkey,
+    pub shares: u64,
+    pub bump: u8,
+}
+
+impl StakeAccount {
+    pub const SIZE: usize = 8 + // discriminator
+        32 + // pool
+        32 + // owner
+        8 + // shares
+        1; // bump
+}
+
+#[error_code]
+pub enum StakingError {
+    #[msg("Unauthorized")]
+    Unauthorized,
+    #[msg("Amount must be greater than zero")]
+    ZeroAmount,
+    #[msg("Reward period must be positive")]
+    InvalidRewardPeriod,
+    #[msg("Withdrawal exceeds staked balance")]
+    InsufficientBalance,
+    #[msg("Calculation overflow")]
+    CalculationOverflow,
+}
+
+#[event]
+pub struct PoolInitialized {
+    pub pool: Pubkey,
+    pub mint: Pubkey,
+    pub reward_bps: u16,
+    pub reward_period: i64,
+}
+
+#[event]
+pub struct RewardsUpdated {
+    pub pool: Pubkey,
+    pub reward_bps: u16,
+    pub reward_period: i64,
+}
+
+#[event]
+pub struct StakeUpdated {
+    pub pool: Pubkey,
+    pub owner: Pubkey,
+    pub amount: u64,
+    pub shares: u64,
+    pub total_staked: u64,
+}
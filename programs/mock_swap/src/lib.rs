@@ -0,0 +1,482 @@
+use anchor_lang::prelude::*;
+use anchor_spl::token::{self, Mint, Token, TokenAccount, Transfer};
+
+// Aethernaut Mock Swap
+// Fixed-rate swap pool; moving the rate gives a held position a deterministic gain or loss
+
+declare_id!("8TPabzUWi5krnTn6pPWjKycr9Xr1KwL5JMoijJhhFNVX");
+
+// Rates are output tokens per input token, fixed-point at RATE_PRECISION
+pub const RATE_PRECISION: u64 = 1_000_000;
+
+#[program]
+pub mod mock_swap {
+    use super::*;
+
+    /// Create a pool swapping `input_mint` into `output_mint` at a fixed rate
+    pub fn initialize_pool(ctx: Context<InitializePool>, rate: u64) -> Result<()> {
+        require!(rate > 0, SwapError::InvalidRate);
+
+        let pool = &mut ctx.accounts.pool;
+        pool.authority = ctx.accounts.authority.key();
+        pool.input_mint = ctx.accounts.input_mint.key();
+        pool.output_mint = ctx.accounts.output_mint.key();
+        pool.input_reserve = ctx.accounts.input_reserve.key();
+        pool.output_reserve = ctx.accounts.output_reserve.key();
+        pool.rate = rate;
+        pool.total_held = 0;
+        pool.bump = ctx.bumps.pool;
+
+        emit!(PoolInitialized {
+            pool: pool.key(),
+            input_mint: pool.input_mint,
+            output_mint: pool.output_mint,
+            rate,
+        });
+
+        Ok(())
+    }
+
+    /// Change the swap rate; holdings are valued at the new rate from now on
+    pub fn set_rate(ctx: Context<SetRate>, rate: u64) -> Result<()> {
+        let pool = &mut ctx.accounts.pool;
+
+        require!(
+            ctx.accounts.authority.key() == pool.authority,
+            SwapError::Unauthorized
+        );
+        require!(rate > 0, SwapError::InvalidRate);
+
+        pool.rate = rate;
+
+        emit!(RateUpdated {
+            pool: pool.key(),
+            rate,
+        });
+
+        Ok(())
+    }
+
+    /// Top up either reserve so swaps and withdrawals can be paid out
+    pub fn fund_reserve(ctx: Context<FundReserve>, amount: u64) -> Result<()> {
+        require!(amount > 0, SwapError::ZeroAmount);
+
+        let cpi_accounts = Transfer {
+            from: ctx.accounts.source.to_account_info(),
+            to: ctx.accounts.reserve.to_account_info(),
+            authority: ctx.accounts.funder.to_account_info(),
+        };
+        let cpi_ctx = CpiContext::new(ctx.accounts.token_program.to_account_info(), cpi_accounts);
+
+        token::transfer(cpi_ctx, amount)
+    }
+
+    /// Exact-input swap in either direction at the pool rate
+    pub fn swap(
+        ctx: Context<Swap>,
+        amount_in: u64,
+        min_amount_out: u64,
+        input_to_output: bool,
+    ) -> Result<()> {
+        require!(amount_in > 0, SwapError::ZeroAmount);
+
+        let pool = &ctx.accounts.pool;
+        let (from_reserve, to_reserve, amount_out) = if input_to_output {
+            (
+                &ctx.accounts.output_reserve,
+                &ctx.accounts.input_reserve,
+                to_output(amount_in, pool.rate)?,
+            )
+        } else {
+            (
+                &ctx.accounts.input_reserve,
+                &ctx.accounts.output_reserve,
+                to_input(amount_in, pool.rate)?,
+            )
+        };
+        require!(amount_out >= min_amount_out, SwapError::SlippageExceeded);
+        require!(amount_out > 0, SwapError::ZeroAmount);
+        // Output held in custody for holdings is not available to plain swaps
+        require!(
+            !input_to_output
+                || ctx.accounts.output_reserve.amount.saturating_sub(amount_out) >= pool.total_held,
+            SwapError::InsufficientLiquidity
+        );
+
+        let cpi_accounts = Transfer {
+            from: ctx.accounts.user_source.to_account_info(),
+            to: to_reserve.to_account_info(),
+            authority: ctx.accounts.user.to_account_info(),
+        };
+        let cpi_ctx = CpiContext::new(ctx.accounts.token_program.to_account_info(), cpi_accounts);
+        token::transfer(cpi_ctx, amount_in)?;
+
+        let input_mint = pool.input_mint;
+        let output_mint = pool.output_mint;
+        let signer_seeds: &[&[&[u8]]] = &[&[
+            b"pool",
+            input_mint.as_ref(),
+            output_mint.as_ref(),
+            &[pool.bump],
+        ]];
+        let cpi_accounts = Transfer {
+            from: from_reserve.to_account_info(),
+            to: ctx.accounts.user_destination.to_account_info(),
+            authority: pool.to_account_info(),
+        };
+        let cpi_ctx = CpiContext::new_with_signer(
+            ctx.accounts.token_program.to_account_info(),
+            cpi_accounts,
+            signer_seeds,
+        );
+        token::transfer(cpi_ctx, amount_out)?;
+
+        emit!(Swapped {
+            pool: pool.key(),
+            user: ctx.accounts.user.key(),
+            amount_in,
+            amount_out,
+            input_to_output,
+        });
+
+        Ok(())
+    }
+
+    /// Open the holding that tracks `owner`'s output balance in custody of the pool
+    pub fn open_holding(ctx: Context<OpenHolding>, owner: Pubkey) -> Result<()> {
+        let holding = &mut ctx.accounts.holding;
+        holding.pool = ctx.accounts.pool.key();
+        holding.owner = owner;
+        holding.output_balance = 0;
+        holding.bump = ctx.bumps.holding;
+
+        Ok(())
+    }
+
+    /// Strategy adapter deposit: swap `amount` of the input mint into output held by the pool
+    pub fn deposit(ctx: Context<Deposit>, amount: u64) -> Result<()> {
+        require!(amount > 0, SwapError::ZeroAmount);
+
+        let pool = &mut ctx.accounts.pool;
+        let holding = &mut ctx.accounts.holding;
+        let output = to_output(amount, pool.rate)?;
+        require!(output > 0, SwapError::ZeroAmount);
+
+        let total_held = pool.total_held.checked_add(output).unwrap();
+        require!(
+            total_held <= ctx.accounts.output_reserve.amount,
+            SwapError::InsufficientLiquidity
+        );
+
+        let cpi_accounts = Transfer {
+            from: ctx.accounts.source.to_account_info(),
+            to: ctx.accounts.input_reserve.to_account_info(),
+            authority: ctx.accounts.owner.to_account_info(),
+        };
+        let cpi_ctx = CpiContext::new(ctx.accounts.token_program.to_account_info(), cpi_accounts);
+        token::transfer(cpi_ctx, amount)?;
+
+        pool.total_held = total_held;
+        holding.output_balance = holding.output_balance.checked_add(output).unwrap();
+
+        emit!(HoldingUpdated {
+            pool: pool.key(),
+            owner: holding.owner,
+            input_amount: amount,
+            output_balance: holding.output_balance,
+        });
+
+        Ok(())
+    }
+
+    /// Strategy adapter withdraw: swap held output back into `amount` of the input mint
+    pub fn withdraw(ctx: Context<Withdraw>, amount: u64) -> Result<()> {
+        require!(amount > 0, SwapError::ZeroAmount);
+
+        let pool = &mut ctx.accounts.pool;
+        let holding = &mut ctx.accounts.holding;
+
+        // Round the output debited up so holders cannot extract dust at the pool's expense
+        let output_needed = (amount as u128)
+            .checked_mul(pool.rate as u128)
+            .map(|v| v.div_ceil(RATE_PRECISION as u128))
+            .and_then(|v| u64::try_from(v).ok())
+            .ok_or(SwapError::CalculationOverflow)?;
+        require!(
+            output_needed <= holding.output_balance,
+            SwapError::InsufficientBalance
+        );
+
+        let input_mint = pool.input_mint;
+        let output_mint = pool.output_mint;
+        let signer_seeds: &[&[&[u8]]] = &[&[
+            b"pool",
+            input_mint.as_ref(),
+            output_mint.as_ref(),
+            &[pool.bump],
+        ]];
+        let cpi_accounts = Transfer {
+            from: ctx.accounts.input_reserve.to_account_info(),
+            to: ctx.accounts.destination.to_account_info(),
+            authority: pool.to_account_info(),
+        };
+        let cpi_ctx = CpiContext::new_with_signer(
+            ctx.accounts.token_program.to_account_info(),
+            cpi_accounts,
+            signer_seeds,
+        );
+        token::transfer(cpi_ctx, amount)?;
+
+        holding.output_balance -= output_needed;
+        pool.total_held = pool.total_held.saturating_sub(output_needed);
+
+        emit!(HoldingUpdated {
+            pool: pool.key(),
+            owner: holding.owner,
+            input_amount: amount,
+            output_balance: holding.output_balance,
+        });
+
+        Ok(())
+    }
+}
+
+/// Output tokens received for `amount` input tokens, rounded down
+pub fn to_output(amount: u64, rate: u64) -> Result<u64> {
+    let output = (amount as u128)
+        .checked_mul(rate as u128)
+        .map(|v| v / RATE_PRECISION as u128)
+        .ok_or(SwapError::CalculationOverflow)?;
+    u64::try_from(output).map_err(|_| SwapError::CalculationOverflow.into())
+}
+
+/// Input tokens received for `amount` output tokens, rounded down
+pub fn to_input(amount: u64, rate: u64) -> Result<u64> {
+    let input = (amount as u128)
+        .checked_mul(RATE_PRECISION as u128)
+        .map(|v| v / rate as u128)
+        .ok_or(SwapError::CalculationOverflow)?;
+    u64::try_from(input).map_err(|_| SwapError::CalculationOverflow.into())
+}
+
+#[derive(Accounts)]
+pub struct InitializePool<'info> {
+    #[account(
+        init,
+        payer = authority,
+        space = SwapPool::SIZE,
+        seeds = [b"pool", input_mint.key().as_ref(), output_mint.key().as_ref()],
+        bump
+    )]
+    pub pool: Account<'info, SwapPool>,
+    pub input_mint: Account<'info, Mint>,
+    pub output_mint: Account<'info, Mint>,
+    #[account(
+        init,
+        payer = authority,
+        token::mint = input_mint,
+        token::authority = pool,
+        seeds = [b"input_reserve", pool.key().as_ref()],
+        bump
+    )]
+    pub input_reserve: Account<'info, TokenAccount>,
+    #[account(
+        init,
+        payer = authority,
+        token::mint = output_mint,
+        token::authority = pool,
+        seeds = [b"output_reserve", pool.key().as_ref()],
+        bump
+    )]
+    pub output_reserve: Account<'info, TokenAccount>,
+    #[account(mut)]
+    pub authority: Signer<'info>,
+    pub token_program: Program<'info, Token>,
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+pub struct SetRate<'info> {
+    #[account(mut)]
+    pub pool: Account<'info, SwapPool>,
+    pub authority: Signer<'info>,
+}
+
+#[derive(Accounts)]
+pub struct FundReserve<'info> {
+    pub pool: Account<'info, SwapPool>,
+    #[account(
+        mut,
+        constraint = reserve.key() == pool.input_reserve
+            || reserve.key() == pool.output_reserve @ SwapError::InvalidReserve
+    )]
+    pub reserve: Account<'info, TokenAccount>,
+    #[account(mut)]
+    pub source: Account<'info, TokenAccount>,
+    pub funder: Signer<'info>,
+    pub token_program: Program<'info, Token>,
+}
+
+#[derive(Accounts)]
+pub struct Swap<'info> {
+    #[account(has_one = input_reserve, has_one = output_reserve)]
+    pub pool: Account<'info, SwapPool>,
+    #[account(mut)]
+    pub input_reserve: Account<'info, TokenAccount>,
+    #[account(mut)]
+    pub output_reserve: Account<'info, TokenAccount>,
+    #[account(mut)]
+    pub user_source: Account<'info, TokenAccount>,
+    #[account(mut)]
+    pub user_destination: Account<'info, TokenAccount>,
+    pub user: Signer<'info>,
+    pub token_program: Program<'info, Token>,
+}
+
+#[derive(Accounts)]
+#[instruction(owner: Pubkey)]
+pub struct OpenHolding<'info> {
+    pub pool: Account<'info, SwapPool>,
+    #[account(
+        init,
+        payer = payer,
+        space = Holding::SIZE,
+        seeds = [b"holding", pool.key().as_ref(), owner.as_ref()],
+        bump
+    )]
+    pub holding: Account<'info, Holding>,
+    #[account(mut)]
+    pub payer: Signer<'info>,
+    pub system_program: Program<'info, System>,
+}
+
+// Adapter layout after (owner, token account, token program): pool, input reserve,
+// output reserve, holding; only the input mint ever moves in or out of the owner
+#[derive(Accounts)]
+pub struct Deposit<'info> {
+    pub owner: Signer<'info>,
+    #[account(mut, token::mint = pool.input_mint)]
+    pub source: Account<'info, TokenAccount>,
+    pub token_program: Program<'info, Token>,
+    #[account(mut, has_one = input_reserve, has_one = output_reserve)]
+    pub pool: Account<'info, SwapPool>,
+    #[account(mut)]
+    pub input_reserve: Account<'info, TokenAccount>,
+    pub output_reserve: Account<'info, TokenAccount>,
+    #[account(
+        mut,
+        seeds = [b"holding", pool.key().as_ref(), owner.key().as_ref()],
+        bump = holding.bump
+    )]
+    pub holding: Account<'info, Holding>,
+}
+
+#[derive(Accounts)]
+pub struct Withdraw<'info> {
+    pub owner: Signer<'info>,
+    #[account(mut, token::mint = pool.input_mint)]
+    pub destination: Account<'info, TokenAccount>,
+    pub token_program: Program<'info, Token>,
+    #[account(mut, has_one = input_reserve, has_one = output_reserve)]
+    pub pool: Account<'info, SwapPool>,
+    #[account(mut)]
+    pub input_reserve: Account<'info, TokenAccount>,
+    pub output_reserve: Account<'info, TokenAccount>,
+    #[account(
+        mut,
+        seeds = [b"holding", pool.key().as_ref(), owner.key().as_ref()],
+        bump = holding.bump
+    )]
+    pub holding: Account<'info, Holding>,
+}
+
+#[account]
+pub struct SwapPool {
+    pub authority: Pubkey,
+    pub input_mint: Pubkey,
+    pub output_mint: Pubkey,
+    pub input_reserve: Pubkey,
+    pub output_reserve: Pubkey,
+    pub rate: u64,       // Output per input, RATE_PRECISION fixed point
+    pub total_held: u64, // Output tokens held in custody for holdings
+    pub bump: u8,
+}
+
+impl SwapPool {
+    pub const SIZE: usize = 8 + // discriminator
+        32 + // authority
+        32 + // input_mint
+        32 + // output_mint
+        32 + // input_reserve
+        32 + // output_reserve
+        8 + // rate
+        8 + // total_held
+        1; // bump
+}
+
+#[account]
+pub struct Holding {
+    pub pool: Pubkey,
+    pub owner: Pubkey,
+    pub output_balance: u64,
+    pub bump: u8,
+}
+
+impl Holding {
+    pub const SIZE: usize = 8 + // discriminator
+        32 + // pool
+        32 + // owner
+        8 + // output_balance
+        1; // bump
+}
+
+#[error_code]
+pub enum SwapError {
+    #[msg("Unauthorized")]
+    Unauthorized,
+    #[msg("Amount must be greater than zero")]
+    ZeroAmount,
+    #[msg("Rate must be greater than zero")]
+    InvalidRate,
+    #[msg("Reserve does not belong to the pool")]
+    InvalidReserve,
+    #[msg("Output below minimum")]
+    SlippageExceeded,
+    #[msg("Output reserve cannot cover holdings")]
+    InsufficientLiquidity,
+    #[msg("Withdrawal exceeds holding balance")]
+    InsufficientBalance,
+    #[msg("Calculation overflow")]
+    CalculationOverflow,
+}
+
+#[event]
+pub struct PoolInitialized {
+    pub pool: Pubkey,
+    pub input_mint: Pubkey,
+    pub output_mint: Pubkey,
+    pub rate: u64,
+}
+
+#[event]
+pub struct RateUpdated {
+    pub pool: Pubkey,
+    pub rate: u64,
+}
+
+#[event]
+pub struct Swapped {
+    pub pool: Pubkey,
+    pub user: Pubkey,
+    pub amount_in: u64,
+    pub amount_out: u64,
+    pub input_to_output: bool,
+}
+
+#[event]
+pub struct HoldingUpdated {
+    pub pool: Pubkey,
+    pub owner: Pubkey,
+    pub input_amount: u64,
+    pub output_balance: u64,
+}
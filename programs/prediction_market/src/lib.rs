@@ -5,17 +5,32 @@ use anchor_lang::prelude::*;
 
 declare_id!("DR9aDNjhEwKmw3KmNkLiNM7Kw8mspMw4KzdhR9xXAFWP");
 
+// Pause flags, one bit per instruction category
+pub const PAUSE_MARKET_CREATION: u16 = 1 << 0;
+pub const PAUSE_BETTING: u16 = 1 << 1;
+pub const PAUSE_RESOLUTION: u16 = 1 << 2;
+pub const PAUSE_CLAIMS: u16 = 1 << 3;
+pub const PAUSE_ALL: u16 = PAUSE_MARKET_CREATION
+    | PAUSE_BETTING
+    | PAUSE_RESOLUTION
+    | PAUSE_CLAIMS;
+
 #[program]
 pub mod prediction_market {
     use super::*;
 
     /// Initialize the prediction market
     pub fn initialize(ctx: Context<Initialize>, params: MarketParams) -> Result<()> {
+        params.validate()?;
+        
         let market = &mut ctx.accounts.market;
         market.authority = ctx.accounts.authority.key();
+        market.pending_authority = None;
         market.params = params;
         market.total_markets = 0;
         market.total_volume = 0;
+        market.guardian = Pubkey::default();
+        market.paused = 0;
         
         emit!(MarketInitialized {
             market: market.key(),
@@ -25,6 +40,148 @@ pub mod prediction_market {
         Ok(())
     }
 
+    /// Propose a new authority; takes effect only once the new key accepts
+    pub fn propose_authority_transfer(
+        ctx: Context<ProposeAuthorityTransfer>,
+        new_authority: Pubkey,
+    ) -> Result<()> {
+        let market = &mut ctx.accounts.market;
+        
+        require!(
+            ctx.accounts.authority.key() == market.authority,
+            MarketError::Unauthorized
+        );
+        
+        market.pending_authority = Some(new_authority);
+        
+        emit!(AuthorityTransferProposed {
+            market: market.key(),
+            current_authority: market.authority,
+            pending_authority: new_authority,
+        });
+        
+        Ok(())
+    }
+
+    /// Pending authority accepts the transfer and becomes the authority
+    pub fn accept_authority_transfer(ctx: Context<AcceptAuthorityTransfer>) -> Result<()> {
+        let market = &mut ctx.accounts.market;
+        
+        require!(
+            market.pending_authority == Some(ctx.accounts.new_authority.key()),
+            MarketError::NotPendingAuthority
+        );
+        
+        let old_authority = market.authority;
+        market.authority = ctx.accounts.new_authority.key();
+        market.pending_authority = None;
+        
+        emit!(AuthorityTransferAccepted {
+            market: market.key(),
+            old_authority,
+            new_authority: market.authority,
+        });
+        
+        Ok(())
+    }
+
+    /// Current authority withdraws a pending transfer
+    pub fn cancel_authority_transfer(ctx: Context<CancelAuthorityTransfer>) -> Result<()> {
+        let market = &mut ctx.accounts.market;
+        
+        require!(
+            ctx.accounts.authority.key() == market.authority,
+            MarketError::Unauthorized
+        );
+        let cancelled_authority = market
+            .pending_authority
+            .take()
+            .ok_or(MarketError::NoPendingAuthority)?;
+        
+        emit!(AuthorityTransferCancelled {
+            market: market.key(),
+            authority: market.authority,
+            cancelled_authority,
+        });
+        
+        Ok(())
+    }
+
+    /// Set the guardian key that may pause, but never unpause, the market
+    pub fn set_guardian(ctx: Context<SetGuardian>, guardian: Pubkey) -> Result<()> {
+        let market = &mut ctx.accounts.market;
+        
+        require!(
+            ctx.accounts.authority.key() == market.authority,
+            MarketError::Unauthorized
+        );
+        
+        let old_guardian = market.guardian;
+        market.guardian = guardian;
+        
+        emit!(GuardianUpdated {
+            market: market.key(),
+            old_guardian,
+            new_guardian: guardian,
+        });
+        
+        Ok(())
+    }
+
+    /// Replace the market parameters
+    /// Existing predictions keep the params copied at creation; only new ones pick up the change
+    pub fn update_params(ctx: Context<UpdateParams>, params: MarketParams) -> Result<()> {
+        let market = &mut ctx.accounts.market;
+        
+        require!(
+            ctx.accounts.authority.key() == market.authority,
+            MarketError::Unauthorized
+        );
+        params.validate()?;
+        
+        let old_params = market.params.clone();
+        market.params = params.clone();
+        
+        emit!(ParamsUpdated {
+            market: market.key(),
+            old_params,
+            new_params: params,
+        });
+        
+        Ok(())
+    }
+
+    /// Update the pause flags
+    /// SECURITY: Authority may set any flags; guardian may only add flags
+    pub fn set_paused(ctx: Context<SetPaused>, flags: u16) -> Result<()> {
+        let market = &mut ctx.accounts.market;
+        let signer = ctx.accounts.signer.key();
+        
+        require!(flags & !PAUSE_ALL == 0, MarketError::InvalidPauseFlags);
+        if signer != market.authority {
+            require!(
+                market.guardian != Pubkey::default() && signer == market.guardian,
+                MarketError::UnauthorizedPauser
+            );
+            require!(
+                flags & market.paused == market.paused,
+                MarketError::GuardianCannotUnpause
+            );
+        }
+        
+        let old_flags = market.paused;
+        market.paused = flags;
+        
+        emit!(PauseUpdated {
+            market: market.key(),
+            old_flags,
+            new_flags: flags,
+            updated_by: signer,
+        });
+        
+        Ok(())
+    }
+
     /// Create a prediction market for a treasury decision
     pub fn create_prediction_market(
         ctx: Context<CreatePredictionMarket>,
@@ -35,7 +192,13 @@ pub mod prediction_market {
         let market = &mut ctx.accounts.market;
         let prediction = &mut ctx.accounts.prediction;
         
+        require!(
+            !market.is_paused(PAUSE_MARKET_CREATION),
+            MarketError::Paused
+        );
+        
         prediction.creator = ctx.accounts.creator.key();
+        prediction.market = market.key();
         prediction.treasury_proposal = treasury_proposal;
         prediction.decision_summary = decision_summary;
         prediction.created_at = Clock::get()?.unix_timestamp;
@@ -70,6 +233,10 @@ pub mod prediction_market {
         let prediction = &mut ctx.accounts.prediction;
         let bet = &mut ctx.accounts.bet;
         
+        require!(
+            !ctx.accounts.market.is_paused(PAUSE_BETTING),
+            MarketError::Paused
+        );
         require!(
             matches!(prediction.status, PredictionStatus::Open),
             MarketError::MarketNotOpen
@@ -106,7 +273,7 @@ pub mod prediction_market {
         let total_pool = prediction.yes_pool + prediction.no_pool;
         if total_pool > 0 {
             prediction.consensus_price = Some(
-                (prediction.yes_pool as u64 * 10000) / total_pool // Basis points
+                (prediction.yes_pool * 10000) / total_pool // Basis points
             );
         }
         
@@ -131,6 +298,10 @@ pub mod prediction_market {
         let prediction = &mut ctx.accounts.prediction;
         let market = &mut ctx.accounts.market;
         
+        require!(
+            !market.is_paused(PAUSE_RESOLUTION),
+            MarketError::Paused
+        );
         require!(
             matches!(prediction.status, PredictionStatus::Open),
             MarketError::MarketNotOpen
@@ -165,6 +336,10 @@ pub mod prediction_market {
         let prediction = &ctx.accounts.prediction;
         let bet = &mut ctx.accounts.bet;
         
+        require!(
+            !ctx.accounts.market.is_paused(PAUSE_CLAIMS),
+            MarketError::Paused
+        );
         require!(
             matches!(prediction.status, PredictionStatus::Resolved),
             MarketError::MarketNotResolved
@@ -254,6 +429,11 @@ pub mod prediction_market {
     pub fn cancel_market(ctx: Context<CancelMarket>) -> Result<()> {
         let prediction = &mut ctx.accounts.prediction;
         
+        // Cancelling is an alternative resolution, so it shares the resolution pause
+        require!(
+            !ctx.accounts.market.is_paused(PAUSE_RESOLUTION),
+            MarketError::Paused
+        );
         require!(
             prediction.total_bets == 0,
             MarketError::BetsAlreadyPlaced
@@ -282,6 +462,48 @@ pub struct Initialize<'info> {
     pub system_program: Program<'info, System>,
 }
 
+#[derive(Accounts)]
+pub struct ProposeAuthorityTransfer<'info> {
+    #[account(mut)]
+    pub market: Account<'info, Market>,
+    pub authority: Signer<'info>,
+}
+
+#[derive(Accounts)]
+pub struct AcceptAuthorityTransfer<'info> {
+    #[account(mut)]
+    pub market: Account<'info, Market>,
+    pub new_authority: Signer<'info>,
+}
+
+#[derive(Accounts)]
+pub struct CancelAuthorityTransfer<'info> {
+    #[account(mut)]
+    pub market: Account<'info, Market>,
+    pub authority: Signer<'info>,
+}
+
+#[derive(Accounts)]
+pub struct SetGuardian<'info> {
+    #[account(mut)]
+    pub market: Account<'info, Market>,
+    pub authority: Signer<'info>,
+}
+
+#[derive(Accounts)]
+pub struct UpdateParams<'info> {
+    #[account(mut)]
+    pub market: Account<'info, Market>,
+    pub authority: Signer<'info>,
+}
+
+#[derive(Accounts)]
+pub struct SetPaused<'info> {
+    #[account(mut)]
+    pub market: Account<'info, Market>,
+    pub signer: Signer<'info>,
+}
+
 #[derive(Accounts)]
 pub struct CreatePredictionMarket<'info> {
     #[account(mut)]
@@ -299,7 +521,8 @@ pub struct CreatePredictionMarket<'info> {
 
 #[derive(Accounts)]
 pub struct PlaceBet<'info> {
-    #[account(mut)]
+    pub market: Account<'info, Market>,
+    #[account(mut, has_one = market)]
     pub prediction: Account<'info, Prediction>,
     #[account(
         init,
@@ -316,14 +539,15 @@ pub struct PlaceBet<'info> {
 pub struct ResolveMarket<'info> {
     #[account(mut)]
     pub market: Account<'info, Market>,
-    #[account(mut)]
+    #[account(mut, has_one = market)]
     pub prediction: Account<'info, Prediction>,
     pub resolver: Signer<'info>,
 }
 
 #[derive(Accounts)]
 pub struct ClaimWinnings<'info> {
-    #[account(mut)]
+    pub market: Account<'info, Market>,
+    #[account(mut, has_one = market)]
     pub prediction: Account<'info, Prediction>,
     #[account(mut)]
     pub bet: Account<'info, Bet>,
@@ -337,6 +561,8 @@ pub struct GetSentiment<'info> {
 
 #[derive(Accounts)]
 pub struct CancelMarket<'info> {
+    #[account(address = prediction.market)]
+    pub market: Account<'info, Market>,
     #[account(mut)]
     pub prediction: Account<'info, Prediction>,
     pub authority: Signer<'info>,
@@ -345,13 +571,20 @@ pub struct CancelMarket<'info> {
 #[account]
 pub struct Market {
     pub authority: Pubkey,
+    pub pending_authority: Option<Pubkey>,
     pub params: MarketParams,
     pub total_markets: u64,
     pub total_volume: u64,
+    pub guardian: Pubkey, // May pause but not unpause
+    pub paused: u16,      // Bitflags of PAUSE_* categories
 }
 
 impl Market {
-    pub const SIZE: usize = 8 + 32 + MarketParams::SIZE + 8 + 8;
+    pub const SIZE: usize = 8 + 32 + (1 + 32) + MarketParams::SIZE + 8 + 8 + 32 + 2;
+
+    pub fn is_paused(&self, flag: u16) -> bool {
+        self.paused & flag != 0
+    }
 }
 
 #[derive(AnchorSerialize, AnchorDeserialize, Clone, Debug)]
@@ -364,11 +597,19 @@ pub struct MarketParams {
 
 impl MarketParams {
     pub const SIZE: usize = 8 + 8 + 2 + 8;
+
+    pub fn validate(&self) -> Result<()> {
+        require!(self.min_bet <= self.max_bet, MarketError::InvalidBetLimits);
+        require!(self.platform_fee_bps <= 10_000, MarketError::InvalidBps);
+        require!(self.resolution_delay >= 0, MarketError::InvalidResolutionDelay);
+        Ok(())
+    }
 }
 
 #[account]
 pub struct Prediction {
     pub creator: Pubkey,
+    pub market: Pubkey,
     pub treasury_proposal: Pubkey,
     pub decision_summary: String,
     pub created_at: i64,
@@ -387,6 +628,7 @@ pub struct Prediction {
 impl Prediction {
     pub const SIZE: usize = 8 + // discriminator
         32 + // creator
+        32 + // market
         32 + // treasury_proposal
         200 + // decision_summary (max 200 chars)
         8 + // created_at
@@ -464,6 +706,26 @@ pub enum MarketError {
     CalculationOverflow,
     #[msg("Winnings too large")]
     WinningsTooLarge,
+    #[msg("Unauthorized")]
+    Unauthorized,
+    #[msg("Signer is not the pending authority")]
+    NotPendingAuthority,
+    #[msg("No authority transfer is pending")]
+    NoPendingAuthority,
+    #[msg("Instruction category is paused")]
+    Paused,
+    #[msg("Unknown pause flags")]
+    InvalidPauseFlags,
+    #[msg("Signer may not change pause flags")]
+    UnauthorizedPauser,
+    #[msg("Guardian can only pause")]
+    GuardianCannotUnpause,
+    #[msg("Minimum bet exceeds maximum bet")]
+    InvalidBetLimits,
+    #[msg("Basis point value exceeds 10,000")]
+    InvalidBps,
+    #[msg("Resolution delay cannot be negative")]
+    InvalidResolutionDelay,
 }
 
 // Events
@@ -473,6 +735,28 @@ pub struct MarketInitialized {
     pub authority: Pubkey,
 }
 
+#[event]
+pub struct GuardianUpdated {
+    pub market: Pubkey,
+    pub old_guardian: Pubkey,
+    pub new_guardian: Pubkey,
+}
+
+#[event]
+pub struct PauseUpdated {
+    pub market: Pubkey,
+    pub old_flags: u16,
+    pub new_flags: u16,
+    pub updated_by: Pubkey,
+}
+
+#[event]
+pub struct ParamsUpdated {
+    pub market: Pubkey,
+    pub old_params: MarketParams,
+    pub new_params: MarketParams,
+}
+
 #[event]
 pub struct PredictionMarketCreated {
     pub prediction: Pubkey,
@@ -520,3 +804,24 @@ pub struct BetLost {
 pub struct MarketCancelled {
     pub prediction: Pubkey,
 }
+
+#[event]
+pub struct AuthorityTransferProposed {
+    pub market: Pubkey,
+    pub current_authority: Pubkey,
+    pub pending_authority: Pubkey,
+}
+
+#[event]
+pub struct AuthorityTransferAccepted {
+    pub market: Pubkey,
+    pub old_authority: Pubkey,
+    pub new_authority: Pubkey,
+}
+
+#[event]
+pub struct AuthorityTransferCancelled {
+    pub market: Pubkey,
+    pub authority: Pubkey,
+    pub cancelled_authority: Pubkey,
+}
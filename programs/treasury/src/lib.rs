@@ -1,24 +1,94 @@
 use anchor_lang::prelude::*;
-use anchor_spl::token::{self, Token, TokenAccount, Transfer};
+use anchor_lang::solana_program::instruction::{AccountMeta, Instruction};
+use anchor_lang::solana_program::program::invoke_signed;
+use anchor_spl::token::{self, Burn, Mint, MintTo, Token, TokenAccount, Transfer};
+use solana_sha256_hasher::{hash, hashv};
+use agent_registry::program::AgentRegistry;
+use agent_registry::{Agent, AgentStatus, AgentType, Registry};
 
 // Aethernaut Treasury Program
 // Core vault management with autonomous yield optimization
 
 declare_id!("EYpZP5URZyGy32ZaHewPRe3S2BBXBAbtqNfyA5GxphUA");
 
+// Virtual offset applied to share pricing so the first depositor cannot
+// inflate the share price and round later deposits down to zero shares.
+// Share mints carry SHARE_DECIMALS_OFFSET more decimals than their asset so one
+// share token still prices at one asset token; the share supply then caps a vault
+// at u64::MAX / VIRTUAL_SHARES base units, which is why asset decimals are capped
+pub const SHARE_DECIMALS_OFFSET: u8 = 3;
+pub const VIRTUAL_SHARES: u64 = 10u64.pow(SHARE_DECIMALS_OFFSET as u32);
+pub const MAX_ASSET_DECIMALS: u8 = 9; // ~18.4M tokens of share capacity at 9 decimals
+pub const VIRTUAL_ASSETS: u64 = 1;
+
+// Share prices are fixed-point with 1.0 (the launch price) at SHARE_PRICE_PRECISION
+pub const SHARE_PRICE_PRECISION: u64 = 1_000_000;
+
+pub const MAX_VAULT_ASSETS: usize = 8;
+
+pub const SECONDS_PER_YEAR: u64 = 31_536_000;
+
+// Upper bound on every duration parameter, so timestamps derived from them cannot overflow
+pub const MAX_PARAM_DURATION: i64 = SECONDS_PER_YEAR as i64;
+
+pub const MAX_ADAPTER_ACCOUNTS: usize = 8;
+pub const MAX_ADAPTER_NAME_LEN: usize = 32; // Used as a PDA seed
+
+// Strategy adapters expose Anchor instructions `deposit(amount)` and `withdraw(amount)`
+// whose accounts start with (vault_authority signer, vault, token_program) followed by
+// the adapter's registered layout. Discriminators are sha256("global:<name>")[..8].
+pub const ADAPTER_DEPOSIT_DISCRIMINATOR: [u8; 8] = [242, 35, 198, 137, 82, 225, 242, 182];
+pub const ADAPTER_WITHDRAW_DISCRIMINATOR: [u8; 8] = [183, 18, 70, 156, 148, 109, 161, 34];
+
+// Rolling outflow window tracked as hourly net outflow buckets
+pub const OUTFLOW_WINDOW_HOURS: usize = 24;
+pub const SECONDS_PER_HOUR: i64 = 3_600;
+
+pub const MAX_REASONING_URI_LEN: usize = 200;
+
+pub const MAX_COUNCIL_MEMBERS: usize = 10;
+pub const MAX_ORACLES: usize = 5;
+
+// Pause flags, one bit per instruction category
+pub const PAUSE_DEPOSITS: u16 = 1 << 0;
+pub const PAUSE_WITHDRAWALS: u16 = 1 << 1;
+pub const PAUSE_PROPOSALS: u16 = 1 << 2;
+pub const PAUSE_VOTING: u16 = 1 << 3;
+pub const PAUSE_EXECUTION: u16 = 1 << 4;
+pub const PAUSE_OUTCOMES: u16 = 1 << 5;
+pub const PAUSE_ALL: u16 = PAUSE_DEPOSITS
+    | PAUSE_WITHDRAWALS
+    | PAUSE_PROPOSALS
+    | PAUSE_VOTING
+    | PAUSE_EXECUTION
+    | PAUSE_OUTCOMES;
+
 #[program]
 pub mod treasury {
     use super::*;
 
     /// Initialize the treasury with governance parameters
     pub fn initialize(ctx: Context<Initialize>, params: TreasuryParams) -> Result<()> {
+        params.validate()?;
+        
         let treasury = &mut ctx.accounts.treasury;
         treasury.authority = ctx.accounts.authority.key();
+        treasury.pending_authority = None;
         treasury.params = params;
         treasury.total_value_locked = 0;
         treasury.current_epoch = 0;
+        treasury.epoch_started_at = Clock::get()?.unix_timestamp;
         treasury.last_rebalance = Clock::get()?.unix_timestamp;
         treasury.decision_count = 0;
+        treasury.vault_authority_bump = 0;
+        treasury.asset_count = 0;
+        treasury.guardian = Pubkey::default();
+        treasury.paused = 0;
+        treasury.epoch_risk_used = 0;
+        treasury.epoch_decisions_executed = 0;
+        treasury.fee_recipient = ctx.accounts.authority.key();
+        treasury.shutdown_at = 0;
+        treasury.registry = ctx.accounts.registry.key();
         
         emit!(TreasuryInitialized {
             treasury: treasury.key(),
@@ -29,214 +99,3337 @@ pub mod treasury {
         Ok(())
     }
 
-    /// Deposit funds into the treasury
-    pub fn deposit(ctx: Context<Deposit>, amount: u64) -> Result<()> {
-        let cpi_accounts = Transfer {
-            from: ctx.accounts.user_token_account.to_account_info(),
-            to: ctx.accounts.treasury_token_account.to_account_info(),
-            authority: ctx.accounts.user.to_account_info(),
-        };
-        let cpi_program = ctx.accounts.token_program.to_account_info();
-        let cpi_ctx = CpiContext::new(cpi_program, cpi_accounts);
+    /// Propose a new authority; takes effect only once the new key accepts
+    pub fn propose_authority_transfer(
+        ctx: Context<ProposeAuthorityTransfer>,
+        new_authority: Pubkey,
+    ) -> Result<()> {
+        let treasury = &mut ctx.accounts.treasury;
         
-        token::transfer(cpi_ctx, amount)?;
+        require!(
+            ctx.accounts.authority.key() == treasury.authority,
+            TreasuryError::Unauthorized
+        );
         
-        let treasury = &mut ctx.accounts.treasury;
-        treasury.total_value_locked = treasury.total_value_locked.checked_add(amount).unwrap();
+        treasury.pending_authority = Some(new_authority);
         
-        emit!(DepositEvent {
+        emit!(AuthorityTransferProposed {
             treasury: treasury.key(),
-            user: ctx.accounts.user.key(),
-            amount,
-            new_tvl: treasury.total_value_locked,
+            current_authority: treasury.authority,
+            pending_authority: new_authority,
         });
         
         Ok(())
     }
 
-    /// AI agent submits a yield optimization decision with reasoning
-    pub fn submit_decision(
-        ctx: Context<SubmitDecision>,
-        decision: YieldDecision,
-        reasoning_hash: [u8; 32],
-    ) -> Result<()> {
-        let treasury = &ctx.accounts.treasury;
-        let proposal = &mut ctx.accounts.proposal;
+    /// Pending authority accepts the transfer and becomes the authority
+    pub fn accept_authority_transfer(ctx: Context<AcceptAuthorityTransfer>) -> Result<()> {
+        let treasury = &mut ctx.accounts.treasury;
         
-        proposal.decision = decision.clone();
-        proposal.reasoning_hash = reasoning_hash;
-        proposal.submitted_at = Clock::get()?.unix_timestamp;
-        proposal.voting_ends_at = proposal.submitted_at + treasury.params.decision_period;
-        proposal.status = ProposalStatus::Voting;
-        proposal.votes_for = 0;
-        proposal.votes_against = 0;
+        require!(
+            treasury.pending_authority == Some(ctx.accounts.new_authority.key()),
+            TreasuryError::NotPendingAuthority
+        );
         
-        emit!(DecisionProposed {
-            proposal: proposal.key(),
-            agent: decision.agent_id,
-            action: decision.action,
-            target_protocol: decision.target_protocol.clone(),
-            amount: decision.amount,
-            reasoning_hash,
+        let old_authority = treasury.authority;
+        treasury.authority = ctx.accounts.new_authority.key();
+        treasury.pending_authority = None;
+        
+        emit!(AuthorityTransferAccepted {
+            treasury: treasury.key(),
+            old_authority,
+            new_authority: treasury.authority,
         });
         
         Ok(())
     }
 
-    /// Execute an approved decision after voting period
-    /// SECURITY: Only treasury authority can execute decisions
-    pub fn execute_decision(ctx: Context<ExecuteDecision>) -> Result<()> {
-        let proposal = &mut ctx.accounts.proposal;
+    /// Current authority withdraws a pending transfer
+    pub fn cancel_authority_transfer(ctx: Context<CancelAuthorityTransfer>) -> Result<()> {
         let treasury = &mut ctx.accounts.treasury;
-        let clock = Clock::get()?;
         
-        // CRITICAL: Verify executor is treasury authority
         require!(
-            ctx.accounts.executor.key() == treasury.authority,
-            TreasuryError::UnauthorizedExecutor
+            ctx.accounts.authority.key() == treasury.authority,
+            TreasuryError::Unauthorized
         );
+        let cancelled_authority = treasury
+            .pending_authority
+            .take()
+            .ok_or(TreasuryError::NoPendingAuthority)?;
+        
+        emit!(AuthorityTransferCancelled {
+            treasury: treasury.key(),
+            authority: treasury.authority,
+            cancelled_authority,
+        });
+        
+        Ok(())
+    }
+
+    /// Set the guardian key that may pause, but never unpause, the treasury
+    pub fn set_guardian(ctx: Context<SetGuardian>, guardian: Pubkey) -> Result<()> {
+        let treasury = &mut ctx.accounts.treasury;
         
         require!(
-            clock.unix_timestamp >= proposal.voting_ends_at,
-            TreasuryError::VotingPeriodActive
+            ctx.accounts.authority.key() == treasury.authority,
+            TreasuryError::Unauthorized
         );
+        
+        let old_guardian = treasury.guardian;
+        treasury.guardian = guardian;
+        
+        emit!(GuardianUpdated {
+            treasury: treasury.key(),
+            old_guardian,
+            new_guardian: guardian,
+        });
+        
+        Ok(())
+    }
+
+    /// Set the owner whose share accounts receive fee shares
+    /// SECURITY: Only treasury authority can redirect fees
+    pub fn set_fee_recipient(ctx: Context<SetFeeRecipient>, fee_recipient: Pubkey) -> Result<()> {
+        let treasury = &mut ctx.accounts.treasury;
+        
         require!(
-            matches!(proposal.status, ProposalStatus::Voting),
-            TreasuryError::InvalidProposalStatus
+            ctx.accounts.authority.key() == treasury.authority,
+            TreasuryError::Unauthorized
         );
         
-        // Check if decision passed (more votes for than against)
-        let passed = proposal.votes_for > proposal.votes_against;
+        let old_recipient = treasury.fee_recipient;
+        treasury.fee_recipient = fee_recipient;
         
-        if passed {
-            // Execute the yield optimization action
-            // This would integrate with Jupiter/Kamino/Marinade
-            proposal.status = ProposalStatus::Executed;
-            treasury.decision_count = treasury.decision_count.checked_add(1).unwrap();
-            treasury.last_rebalance = clock.unix_timestamp;
-            
-            emit!(DecisionExecuted {
-                proposal: proposal.key(),
-                decision: proposal.decision.clone(),
-                execution_time: clock.unix_timestamp,
-            });
-        } else {
-            proposal.status = ProposalStatus::Rejected;
-            
-            emit!(DecisionRejected {
-                proposal: proposal.key(),
-                votes_for: proposal.votes_for,
-                votes_against: proposal.votes_against,
-            });
-        }
+        emit!(FeeRecipientUpdated {
+            treasury: treasury.key(),
+            old_recipient,
+            new_recipient: fee_recipient,
+        });
         
         Ok(())
     }
 
-    /// Record the result of an executed decision for learning
-    pub fn record_outcome(
-        ctx: Context<RecordOutcome>,
-        actual_yield_bps: u16,
-        success: bool,
+    /// Queue replacement treasury parameters and pinned registry behind the execution timelock
+    /// SECURITY: Only treasury authority can propose; council or guardian can veto until applied
+    pub fn propose_params_update(
+        ctx: Context<ProposeParamsUpdate>,
+        params: TreasuryParams,
     ) -> Result<()> {
-        let proposal = &mut ctx.accounts.proposal;
+        let treasury = &ctx.accounts.treasury;
+        let change = &mut ctx.accounts.params_change;
+        
         require!(
-            matches!(proposal.status, ProposalStatus::Executed),
-            TreasuryError::DecisionNotExecuted
+            ctx.accounts.authority.key() == treasury.authority,
+            TreasuryError::Unauthorized
         );
+        params.validate()?;
         
-        proposal.outcome = Some(Outcome {
-            actual_yield_bps,
-            success,
-            recorded_at: Clock::get()?.unix_timestamp,
+        change.treasury = treasury.key();
+        change.proposer = ctx.accounts.authority.key();
+        change.new_params = params;
+        change.new_registry = ctx.accounts.registry.key();
+        change.eta = Clock::get()?
+            .unix_timestamp
+            .checked_add(treasury.params.execution_delay)
+            .ok_or(TreasuryError::CalculationOverflow)?;
+        change.bump = ctx.bumps.params_change;
+        
+        emit!(ParamsUpdateProposed {
+            treasury: treasury.key(),
+            proposer: change.proposer,
+            new_params: change.new_params.clone(),
+            new_registry: change.new_registry,
+            eta: change.eta,
         });
         
-        emit!(OutcomeRecorded {
-            proposal: proposal.key(),
-            agent: proposal.decision.agent_id,
-            predicted_yield_bps: proposal.decision.expected_yield_bps,
-            actual_yield_bps,
-            success,
+        Ok(())
+    }
+
+    /// Apply a queued parameter change once its timelock has elapsed
+    pub fn update_params(ctx: Context<UpdateParams>) -> Result<()> {
+        let treasury = &mut ctx.accounts.treasury;
+        let change = &ctx.accounts.params_change;
+        
+        require!(
+            ctx.accounts.authority.key() == treasury.authority,
+            TreasuryError::Unauthorized
+        );
+        require!(
+            Clock::get()?.unix_timestamp >= change.eta,
+            TreasuryError::TimelockActive
+        );
+        // Ranges are rechecked in case validation tightened since the change was queued
+        change.new_params.validate()?;
+        
+        let old_params = std::mem::replace(&mut treasury.params, change.new_params.clone());
+        let old_registry = std::mem::replace(&mut treasury.registry, change.new_registry);
+        
+        emit!(ParamsUpdated {
+            treasury: treasury.key(),
+            old_params,
+            new_params: treasury.params.clone(),
+            old_registry,
+            new_registry: treasury.registry,
         });
         
         Ok(())
     }
-}
 
-#[derive(Accounts)]
-pub struct Initialize<'info> {
-    #[account(init, payer = authority, space = Treasury::SIZE)]
-    pub treasury: Account<'info, Treasury>,
-    #[account(mut)]
-    pub authority: Signer<'info>,
-    pub system_program: Program<'info, System>,
-}
+    /// Drop a queued parameter change and refund its rent to the proposer
+    /// SECURITY: Authority may withdraw its own change; council members and the guardian may veto it
+    pub fn cancel_params_update(ctx: Context<CancelParamsUpdate>) -> Result<()> {
+        let treasury = &ctx.accounts.treasury;
+        let signer = ctx.accounts.signer.key();
+        
+        let is_council_member = ctx
+            .accounts
+            .council
+            .as_ref()
+            .is_some_and(|council| council.is_member(&signer));
+        require!(
+            signer == treasury.authority || is_council_member || signer == treasury.guardian,
+            TreasuryError::UnauthorizedVeto
+        );
+        
+        emit!(ParamsUpdateCancelled {
+            treasury: treasury.key(),
+            cancelled_by: signer,
+        });
+        
+        Ok(())
+    }
 
-#[derive(Accounts)]
-pub struct Deposit<'info> {
-    #[account(mut)]
-    pub treasury: Account<'info, Treasury>,
-    pub user: Signer<'info>,
-    #[account(mut)]
-    pub user_token_account: Account<'info, TokenAccount>,
-    #[account(mut)]
-    pub treasury_token_account: Account<'info, TokenAccount>,
-    pub token_program: Program<'info, Token>,
-}
+    /// Update the pause flags
+    /// SECURITY: Authority may set any flags; guardian may only add flags
+    pub fn set_paused(ctx: Context<SetPaused>, flags: u16) -> Result<()> {
+        let treasury = &mut ctx.accounts.treasury;
+        let signer = ctx.accounts.signer.key();
+        
+        require!(flags & !PAUSE_ALL == 0, TreasuryError::InvalidPauseFlags);
+        if signer != treasury.authority {
+            require!(
+                treasury.guardian != Pubkey::default() && signer == treasury.guardian,
+                TreasuryError::UnauthorizedPauser
+            );
+            require!(
+                flags & treasury.paused == treasury.paused,
+                TreasuryError::GuardianCannotUnpause
+            );
+        }
+        
+        let old_flags = treasury.paused;
+        treasury.paused = flags;
+        
+        emit!(PauseUpdated {
+            treasury: treasury.key(),
+            old_flags,
+            new_flags: flags,
+            updated_by: signer,
+        });
+        
+        Ok(())
+    }
 
-#[derive(Accounts)]
-pub struct SubmitDecision<'info> {
-    #[account(mut)]
-    pub treasury: Account<'info, Treasury>,
-    #[account(
-        init,
-        payer = agent,
-        space = 1024,
-        seeds = [b"proposal", treasury.key().as_ref(), agent.key().as_ref()],
-        bump
-    )]
-    pub proposal: Account<'info, Proposal>,
-    #[account(mut)]
-    pub agent: Signer<'info>,
-    pub system_program: Program<'info, System>,
-}
+    /// Whitelist a mint, creating its PDA-owned vault token account and share mint
+    /// SECURITY: Only treasury authority can whitelist assets
+    pub fn whitelist_asset(ctx: Context<WhitelistAsset>) -> Result<()> {
+        let treasury = &mut ctx.accounts.treasury;
+        let vault_asset = &mut ctx.accounts.vault_asset;
+        
+        require!(
+            ctx.accounts.authority.key() == treasury.authority,
+            TreasuryError::Unauthorized
+        );
+        
+        // Re-whitelisting a delisted asset keeps its balance and share mint
+        if vault_asset.treasury == Pubkey::default() {
+            require!(
+                (treasury.asset_count as usize) < MAX_VAULT_ASSETS,
+                TreasuryError::TooManyAssets
+            );
+            vault_asset.treasury = treasury.key();
+            vault_asset.mint = ctx.accounts.asset_mint.key();
+            vault_asset.vault = ctx.accounts.vault.key();
+            vault_asset.share_mint = ctx.accounts.share_mint.key();
+            vault_asset.balance = 0;
+            vault_asset.deployed = 0;
+            vault_asset.decimals = ctx.accounts.asset_mint.decimals;
+            vault_asset.bump = ctx.bumps.vault_asset;
+            vault_asset.high_water_mark = SHARE_PRICE_PRECISION;
+            vault_asset.fees_accrued_at = Clock::get()?.unix_timestamp;
+            vault_asset.counters_epoch = treasury.current_epoch;
+            vault_asset.epoch_realized_yield = 0;
+            vault_asset.epoch_fees = 0;
+            vault_asset.epoch_net_outflow = 0;
+            vault_asset.outflow_window_hour = Clock::get()?.unix_timestamp.div_euclid(SECONDS_PER_HOUR);
+            vault_asset.hourly_net_outflow = [0; OUTFLOW_WINDOW_HOURS];
+            
+            treasury.asset_count = treasury.asset_count.checked_add(1).unwrap();
+        }
+        vault_asset.enabled = true;
+        treasury.vault_authority_bump = ctx.bumps.vault_authority;
+        
+        emit!(AssetWhitelisted {
+            treasury: treasury.key(),
+            vault_asset: vault_asset.key(),
+            mint: vault_asset.mint,
+            vault: vault_asset.vault,
+            share_mint: vault_asset.share_mint,
+            decimals: vault_asset.decimals,
+        });
+        
+        Ok(())
+    }
 
-#[derive(Accounts)]
-pub struct ExecuteDecision<'info> {
-    #[account(mut)]
-    pub treasury: Account<'info, Treasury>,
-    #[account(mut)]
-    pub proposal: Account<'info, Proposal>,
-    pub executor: Signer<'info>,
-}
+    /// Delist a mint so it no longer accepts deposits or new decisions
+    /// Withdrawals stay open so holders can always exit a delisted asset
+    pub fn delist_asset(ctx: Context<DelistAsset>) -> Result<()> {
+        let treasury = &ctx.accounts.treasury;
+        let vault_asset = &mut ctx.accounts.vault_asset;
+        
+        require!(
+            ctx.accounts.authority.key() == treasury.authority,
+            TreasuryError::Unauthorized
+        );
+        require!(vault_asset.enabled, TreasuryError::AssetNotEnabled);
+        
+        vault_asset.enabled = false;
+        
+        emit!(AssetDelisted {
+            treasury: treasury.key(),
+            vault_asset: vault_asset.key(),
+            mint: vault_asset.mint,
+        });
+        
+        Ok(())
+    }
 
-#[derive(Accounts)]
-pub struct RecordOutcome<'info> {
-    #[account(mut)]
-    pub treasury: Account<'info, Treasury>,
-    #[account(mut)]
-    pub proposal: Account<'info, Proposal>,
-    pub recorder: Signer<'info>,
-}
+    /// Register or update the strategy adapter that executes decisions for a protocol and mint
+    /// SECURITY: Only treasury authority can register adapters; adapter programs are
+    /// trusted with the vault authority signature for the accounts in their layout
+    pub fn register_strategy_adapter(
+        ctx: Context<RegisterStrategyAdapter>,
+        target_protocol: String,
+        program_id: Pubkey,
+        accounts: Vec<AdapterAccount>,
+    ) -> Result<()> {
+        let treasury = &ctx.accounts.treasury;
+        let adapter = &mut ctx.accounts.strategy_adapter;
+        let position = &mut ctx.accounts.position;
+        
+        require!(
+            ctx.accounts.authority.key() == treasury.authority,
+            TreasuryError::Unauthorized
+        );
+        require!(
+            !target_protocol.is_empty() && target_protocol.len() <= MAX_ADAPTER_NAME_LEN,
+            TreasuryError::InvalidAdapterName
+        );
+        require!(
+            accounts.len() <= MAX_ADAPTER_ACCOUNTS,
+            TreasuryError::TooManyAdapterAccounts
+        );
+        // Repointing an adapter with deployed funds would strand them in the old program
+        require!(
+            position.principal == 0 || adapter.program_id == program_id,
+            TreasuryError::AdapterHasDeployedFunds
+        );
+        
+        adapter.treasury = treasury.key();
+        adapter.mint = ctx.accounts.asset_mint.key();
+        adapter.target_protocol = target_protocol.clone();
+        adapter.program_id = program_id;
+        adapter.accounts = accounts;
+        adapter.enabled = true;
+        adapter.bump = ctx.bumps.strategy_adapter;
+        
+        // The position ledger entry lives alongside its adapter and starts empty
+        if position.treasury == Pubkey::default() {
+            position.treasury = treasury.key();
+            position.mint = adapter.mint;
+            position.target_protocol = target_protocol;
+            position.adapter = adapter.key();
+            position.principal = 0;
+            position.marked_value = 0;
+            position.last_marked_at = 0;
+            position.opened_at = 0;
+            position.opened_by = Pubkey::default();
+            position.bump = ctx.bumps.position;
+        }
+        
+        emit!(StrategyAdapterRegistered {
+            treasury: treasury.key(),
+            adapter: adapter.key(),
+            mint: adapter.mint,
+            target_protocol: adapter.target_protocol.clone(),
+            program_id,
+        });
+        
+        Ok(())
+    }
 
-#[account]
-pub struct Treasury {
-    pub authority: Pubkey,
-    pub params: TreasuryParams,
-    pub total_value_locked: u64,
-    pub current_epoch: u64,
-    pub last_rebalance: i64,
-    pub decision_count: u64,
+    /// Disable an adapter for new deposits; withdrawals stay open to unwind deployed funds
+    pub fn disable_strategy_adapter(ctx: Context<DisableStrategyAdapter>) -> Result<()> {
+        let treasury = &ctx.accounts.treasury;
+        let adapter = &mut ctx.accounts.strategy_adapter;
+        
+        require!(
+            ctx.accounts.authority.key() == treasury.authority,
+            TreasuryError::Unauthorized
+        );
+        require!(adapter.enabled, TreasuryError::AdapterNotEnabled);
+        
+        adapter.enabled = false;
+        
+        emit!(StrategyAdapterDisabled {
+            treasury: treasury.key(),
+            adapter: adapter.key(),
+        });
+        
+        Ok(())
+    }
+
+    /// Mark a position to its current value; the change flows into TVL and share prices
+    /// SECURITY: Only treasury authority or a registered oracle can mark positions
+    pub fn mark_position(ctx: Context<MarkPosition>, marked_value: u64) -> Result<()> {
+        let recorder = ctx.accounts.recorder.key();
+        
+        require!(
+            !ctx.accounts.treasury.is_paused(PAUSE_OUTCOMES),
+            TreasuryError::Paused
+        );
+        // SECURITY: Emergency exits are priced on total assets, so marks freeze at
+        // shutdown; only force_unwind writes positions down, from amounts received
+        require!(!ctx.accounts.treasury.is_shut_down(), TreasuryError::TreasuryShutDown);
+        require!(
+            recorder == ctx.accounts.treasury.authority
+                || ctx
+                    .accounts
+                    .oracle_set
+                    .as_ref()
+                    .is_some_and(|oracle_set| oracle_set.oracles.contains(&recorder)),
+            TreasuryError::UnauthorizedRecorder
+        );
+        
+        let position = &mut ctx.accounts.position;
+        let vault_asset = &mut ctx.accounts.vault_asset;
+        let treasury = &mut ctx.accounts.treasury;
+        let previous_value = position.marked_value;
+        
+        vault_asset.deployed = vault_asset
+            .deployed
+            .checked_sub(previous_value)
+            .and_then(|deployed| deployed.checked_add(marked_value))
+            .ok_or(TreasuryError::CalculationOverflow)?;
+        treasury.total_value_locked = treasury
+            .total_value_locked
+            .checked_sub(previous_value)
+            .and_then(|tvl| tvl.checked_add(marked_value))
+            .ok_or(TreasuryError::CalculationOverflow)?;
+        position.marked_value = marked_value;
+        position.last_marked_at = Clock::get()?.unix_timestamp;
+        
+        emit!(PositionMarked {
+            position: position.key(),
+            previous_value,
+            marked_value,
+            new_tvl: treasury.total_value_locked,
+            recorder,
+        });
+        
+        Ok(())
+    }
+
+    /// Deposit an asset into its vault and mint shares pro-rata to the asset balance
+    pub fn deposit(ctx: Context<Deposit>, amount: u64) -> Result<()> {
+        require!(
+            !ctx.accounts.treasury.is_paused(PAUSE_DEPOSITS),
+            TreasuryError::Paused
+        );
+        require!(!ctx.accounts.treasury.is_shut_down(), TreasuryError::TreasuryShutDown);
+        require!(amount > 0, TreasuryError::ZeroAmount);
+        require!(
+            amount >= ctx.accounts.treasury.params.min_deposit,
+            TreasuryError::DepositTooSmall
+        );
+        require!(ctx.accounts.vault_asset.enabled, TreasuryError::AssetNotEnabled);
+        
+        // Fees crystallize before pricing so depositors neither pay nor dodge accrued fees
+        accrue_asset_fees(
+            &ctx.accounts.treasury,
+            &mut ctx.accounts.vault_asset,
+            &mut ctx.accounts.share_mint,
+            &ctx.accounts.fee_share_account,
+            &ctx.accounts.vault_authority,
+            &ctx.accounts.token_program,
+        )?;
+        
+        let shares = shares_for_deposit(
+            amount,
+            ctx.accounts.vault_asset.total_assets(),
+            ctx.accounts.share_mint.supply,
+        )
+        .ok_or(TreasuryError::CalculationOverflow)?;
+        require!(shares > 0, TreasuryError::ZeroShares);
+        
+        let cpi_accounts = Transfer {
+            from: ctx.accounts.user_token_account.to_account_info(),
+            to: ctx.accounts.treasury_token_account.to_account_info(),
+            authority: ctx.accounts.user.to_account_info(),
+        };
+        let cpi_program = ctx.accounts.token_program.to_account_info();
+        let cpi_ctx = CpiContext::new(cpi_program, cpi_accounts);
+        
+        token::transfer(cpi_ctx, amount)?;
+        
+        let treasury_key = ctx.accounts.treasury.key();
+        let signer_seeds: &[&[&[u8]]] = &[&[
+            b"vault_authority",
+            treasury_key.as_ref(),
+            &[ctx.accounts.treasury.vault_authority_bump],
+        ]];
+        let cpi_accounts = MintTo {
+            mint: ctx.accounts.share_mint.to_account_info(),
+            to: ctx.accounts.user_share_account.to_account_info(),
+            authority: ctx.accounts.vault_authority.to_account_info(),
+        };
+        let cpi_program = ctx.accounts.token_program.to_account_info();
+        let cpi_ctx = CpiContext::new_with_signer(cpi_program, cpi_accounts, signer_seeds);
+        
+        token::mint_to(cpi_ctx, shares)?;
+        
+        let vault_asset = &mut ctx.accounts.vault_asset;
+        vault_asset.balance = vault_asset.balance.checked_add(amount).unwrap();
+        let inflow = i64::try_from(amount).map_err(|_| TreasuryError::CalculationOverflow)?;
+        apply_net_outflow(
+            &ctx.accounts.treasury,
+            vault_asset,
+            -inflow,
+            Clock::get()?.unix_timestamp,
+        )?;
+        
+        let treasury = &mut ctx.accounts.treasury;
+        treasury.total_value_locked = treasury.total_value_locked.checked_add(amount).unwrap();
+        
+        emit!(DepositEvent {
+            treasury: treasury.key(),
+            user: ctx.accounts.user.key(),
+            mint: vault_asset.mint,
+            amount,
+            shares_minted: shares,
+            new_asset_balance: vault_asset.balance,
+            new_tvl: treasury.total_value_locked,
+        });
+        
+        Ok(())
+    }
+
+    /// Burn shares and withdraw the pro-rata amount of the underlying asset
+    pub fn withdraw(ctx: Context<Withdraw>, shares: u64) -> Result<()> {
+        require!(
+            !ctx.accounts.treasury.is_paused(PAUSE_WITHDRAWALS),
+            TreasuryError::Paused
+        );
+        // Once shut down, exits go through emergency_withdraw so everyone is paid pro-rata
+        require!(!ctx.accounts.treasury.is_shut_down(), TreasuryError::TreasuryShutDown);
+        require!(shares > 0, TreasuryError::ZeroShares);
+        
+        accrue_asset_fees(
+            &ctx.accounts.treasury,
+            &mut ctx.accounts.vault_asset,
+            &mut ctx.accounts.share_mint,
+            &ctx.accounts.fee_share_account,
+            &ctx.accounts.vault_authority,
+            &ctx.accounts.token_program,
+        )?;
+        
+        let amount = assets_for_shares(
+            shares,
+            ctx.accounts.vault_asset.total_assets(),
+            ctx.accounts.share_mint.supply,
+        )
+        .ok_or(TreasuryError::CalculationOverflow)?;
+        require!(amount > 0, TreasuryError::ZeroAmount);
+        require!(
+            amount <= ctx.accounts.vault_asset.balance,
+            TreasuryError::InsufficientIdleBalance
+        );
+        apply_net_outflow(
+            &ctx.accounts.treasury,
+            &mut ctx.accounts.vault_asset,
+            i64::try_from(amount).map_err(|_| TreasuryError::CalculationOverflow)?,
+            Clock::get()?.unix_timestamp,
+        )?;
+        
+        let cpi_accounts = Burn {
+            mint: ctx.accounts.share_mint.to_account_info(),
+            from: ctx.accounts.user_share_account.to_account_info(),
+            authority: ctx.accounts.user.to_account_info(),
+        };
+        let cpi_program = ctx.accounts.token_program.to_account_info();
+        let cpi_ctx = CpiContext::new(cpi_program, cpi_accounts);
+        
+        token::burn(cpi_ctx, shares)?;
+        
+        let treasury_key = ctx.accounts.treasury.key();
+        let signer_seeds: &[&[&[u8]]] = &[&[
+            b"vault_authority",
+            treasury_key.as_ref(),
+            &[ctx.accounts.treasury.vault_authority_bump],
+        ]];
+        let cpi_accounts = Transfer {
+            from: ctx.accounts.treasury_token_account.to_account_info(),
+            to: ctx.accounts.user_token_account.to_account_info(),
+            authority: ctx.accounts.vault_authority.to_account_info(),
+        };
+        let cpi_program = ctx.accounts.token_program.to_account_info();
+        let cpi_ctx = CpiContext::new_with_signer(cpi_program, cpi_accounts, signer_seeds);
+        
+        token::transfer(cpi_ctx, amount)?;
+        
+        let vault_asset = &mut ctx.accounts.vault_asset;
+        vault_asset.balance = vault_asset.balance.checked_sub(amount).unwrap();
+        
+        let treasury = &mut ctx.accounts.treasury;
+        treasury.total_value_locked = treasury.total_value_locked.checked_sub(amount).unwrap();
+        
+        emit!(WithdrawEvent {
+            treasury: treasury.key(),
+            user: ctx.accounts.user.key(),
+            mint: vault_asset.mint,
+            shares_burned: shares,
+            amount,
+            new_asset_balance: vault_asset.balance,
+            new_tvl: treasury.total_value_locked,
+        });
+        
+        Ok(())
+    }
+
+    /// AI agent submits a yield optimization decision with reasoning
+    pub fn submit_decision(
+        ctx: Context<SubmitDecision>,
+        decision: YieldDecision,
+        reasoning_hash: [u8; 32],
+    ) -> Result<()> {
+        let treasury = &ctx.accounts.treasury;
+        let proposal = &mut ctx.accounts.proposal;
+        let counter = &mut ctx.accounts.proposal_counter;
+        let submitter_agent = &ctx.accounts.submitter_agent;
+        
+        require!(!treasury.is_paused(PAUSE_PROPOSALS), TreasuryError::Paused);
+        require!(!treasury.is_shut_down(), TreasuryError::TreasuryShutDown);
+        require!(ctx.accounts.vault_asset.enabled, TreasuryError::AssetNotEnabled);
+        
+        // SECURITY: Account<Agent> already checks the agent_registry owner and
+        // discriminator; the decision must be submitted by the agent it names
+        require!(
+            decision.agent_id == submitter_agent.key(),
+            TreasuryError::UnregisteredAgent
+        );
+        require!(
+            submitter_agent.owner == ctx.accounts.agent.key(),
+            TreasuryError::AgentOwnerMismatch
+        );
+        require!(
+            matches!(submitter_agent.status, AgentStatus::Active),
+            TreasuryError::AgentNotActive
+        );
+        require!(
+            submitter_agent.reputation_score >= treasury.params.min_proposer_reputation,
+            TreasuryError::InsufficientReputation
+        );
+        require!(
+            treasury.params.allowed_proposer_types & agent_type_flag(&submitter_agent.agent_type)
+                != 0,
+            TreasuryError::AgentTypeNotAllowed
+        );
+        
+        // Allocation limit: max_allocation_bps of the asset's own holdings
+        // Treasury-wide TVL sums raw units across mints, so it is not a meaningful base
+        let max_allocation_bps = treasury.params.max_allocation_bps as u128;
+        require!(
+            (decision.amount as u128) * 10_000
+                <= (ctx.accounts.vault_asset.total_assets() as u128) * max_allocation_bps,
+            TreasuryError::AllocationExceedsAssetLimit
+        );
+        
+        // Only a rebalance names a source, and it must differ from the target
+        let source_valid = match decision.action {
+            YieldAction::Rebalance => {
+                !decision.source_protocol.is_empty()
+                    && decision.source_protocol != decision.target_protocol
+            }
+            _ => decision.source_protocol.is_empty(),
+        };
+        require!(source_valid, TreasuryError::InvalidSourceProtocol);
+        
+        // Risk limits: per-decision cap and remaining per-epoch budget
+        require!(decision.risk_score <= 100, TreasuryError::InvalidRiskScore);
+        require!(
+            decision.risk_score <= treasury.params.max_risk_score,
+            TreasuryError::RiskScoreTooHigh
+        );
+        require!(
+            treasury.epoch_risk_used.saturating_add(decision.risk_score as u64)
+                <= treasury.params.epoch_risk_budget,
+            TreasuryError::EpochRiskBudgetExceeded
+        );
+        
+        if counter.treasury == Pubkey::default() {
+            counter.treasury = treasury.key();
+            counter.agent = ctx.accounts.agent.key();
+            counter.next_sequence = 0;
+            counter.bump = ctx.bumps.proposal_counter;
+        }
+        let sequence = counter.next_sequence;
+        counter.next_sequence = counter.next_sequence.checked_add(1).unwrap();
+        
+        proposal.treasury = treasury.key();
+        proposal.proposer = ctx.accounts.agent.key();
+        proposal.sequence = sequence;
+        proposal.decision = decision.clone();
+        proposal.reasoning_hash = reasoning_hash;
+        proposal.reasoning_revealed = false;
+        proposal.reasoning_uri = String::new();
+        proposal.reasoning_encoding = ContentEncoding::Identity;
+        proposal.submitted_at = Clock::get()?.unix_timestamp;
+        proposal.voting_ends_at = proposal
+            .submitted_at
+            .checked_add(treasury.params.decision_period)
+            .ok_or(TreasuryError::CalculationOverflow)?;
+        proposal.status = ProposalStatus::Voting;
+        proposal.votes_for = 0;
+        proposal.votes_against = 0;
+        proposal.eta = 0;
+        proposal.finalized_at = 0;
+        
+        emit!(DecisionProposed {
+            proposal: proposal.key(),
+            proposer: proposal.proposer,
+            sequence,
+            agent: decision.agent_id,
+            asset_mint: decision.asset_mint,
+            action: decision.action,
+            target_protocol: decision.target_protocol.clone(),
+            source_protocol: decision.source_protocol.clone(),
+            amount: decision.amount,
+            reasoning_hash,
+        });
+        
+        Ok(())
+    }
+
+    /// Proposer reveals where the committed reasoning lives and proves it matches the hash
+    /// Inline reveals hash the reasoning bytes; digest reveals hash the URI, encoding tag
+    /// and document digest together so the commitment also binds the location
+    pub fn reveal_reasoning(
+        ctx: Context<RevealReasoning>,
+        uri: String,
+        content_encoding: ContentEncoding,
+        reasoning: RevealedReasoning,
+    ) -> Result<()> {
+        let proposal = &mut ctx.accounts.proposal;
+        
+        require!(
+            !ctx.accounts.treasury.is_paused(PAUSE_PROPOSALS),
+            TreasuryError::Paused
+        );
+        require!(!ctx.accounts.treasury.is_shut_down(), TreasuryError::TreasuryShutDown);
+        require!(
+            !proposal.reasoning_revealed,
+            TreasuryError::ReasoningAlreadyRevealed
+        );
+        require!(
+            !uri.is_empty() && uri.len() <= MAX_REASONING_URI_LEN,
+            TreasuryError::InvalidReasoningUri
+        );
+        
+        let computed = match &reasoning {
+            RevealedReasoning::Inline(bytes) => hash(bytes),
+            RevealedReasoning::Digest(digest) => hashv(&[
+                uri.as_bytes(),
+                &[content_encoding.clone() as u8],
+                digest,
+            ]),
+        };
+        require!(
+            computed.to_bytes() == proposal.reasoning_hash,
+            TreasuryError::ReasoningHashMismatch
+        );
+        
+        proposal.reasoning_revealed = true;
+        proposal.reasoning_uri = uri;
+        proposal.reasoning_encoding = content_encoding;
+        
+        emit!(ReasoningRevealed {
+            proposal: proposal.key(),
+            uri: proposal.reasoning_uri.clone(),
+            content_encoding: proposal.reasoning_encoding.clone(),
+        });
+        
+        Ok(())
+    }
+
+    /// Cast a reputation-weighted vote on a proposal during its voting period
+    pub fn cast_vote(ctx: Context<CastVote>, support: bool) -> Result<()> {
+        let proposal = &mut ctx.accounts.proposal;
+        let vote = &mut ctx.accounts.vote;
+        let voter_agent = &ctx.accounts.voter_agent;
+        let clock = Clock::get()?;
+        
+        require!(
+            !ctx.accounts.treasury.is_paused(PAUSE_VOTING),
+            TreasuryError::Paused
+        );
+        require!(!ctx.accounts.treasury.is_shut_down(), TreasuryError::TreasuryShutDown);
+        
+        // SECURITY: Account<Agent> already checks the agent_registry owner and
+        // discriminator; the signer must also own the registered agent
+        require!(
+            voter_agent.owner == ctx.accounts.voter.key(),
+            TreasuryError::AgentOwnerMismatch
+        );
+        require!(
+            matches!(voter_agent.status, AgentStatus::Active),
+            TreasuryError::AgentNotActive
+        );
+        // SECURITY: Registration is permissionless and agents start at 500, so
+        // fresh agents must earn reputation before their votes count
+        require!(
+            voter_agent.reputation_score >= ctx.accounts.treasury.params.min_voter_reputation,
+            TreasuryError::InsufficientReputation
+        );
+        
+        require!(
+            matches!(proposal.status, ProposalStatus::Voting),
+            TreasuryError::InvalidProposalStatus
+        );
+        require!(
+            clock.unix_timestamp < proposal.voting_ends_at,
+            TreasuryError::VotingPeriodEnded
+        );
+        require!(
+            !ctx.accounts.treasury.params.require_reasoning_reveal || proposal.reasoning_revealed,
+            TreasuryError::ReasoningNotRevealed
+        );
+        
+        // Vote weight is the agent's reputation (0-1000)
+        let weight = voter_agent.reputation_score as u64;
+        require!(weight > 0, TreasuryError::ZeroVotingWeight);
+        
+        vote.proposal = proposal.key();
+        vote.voter = ctx.accounts.voter.key();
+        vote.agent = voter_agent.key();
+        vote.support = support;
+        vote.weight = weight;
+        vote.voted_at = clock.unix_timestamp;
+        
+        if support {
+            proposal.votes_for = proposal.votes_for.checked_add(weight).unwrap();
+        } else {
+            proposal.votes_against = proposal.votes_against.checked_add(weight).unwrap();
+        }
+        
+        emit!(VoteCast {
+            proposal: proposal.key(),
+            voter: vote.voter,
+            agent: vote.agent,
+            support,
+            weight,
+            votes_for: proposal.votes_for,
+            votes_against: proposal.votes_against,
+        });
+        
+        Ok(())
+    }
+
+    /// Create the M-of-N council that approves decision execution
+    /// SECURITY: Only treasury authority can create the council
+    pub fn initialize_council(
+        ctx: Context<InitializeCouncil>,
+        members: Vec<Pubkey>,
+        threshold: u8,
+    ) -> Result<()> {
+        require!(
+            ctx.accounts.authority.key() == ctx.accounts.treasury.authority,
+            TreasuryError::Unauthorized
+        );
+        validate_council(&members, threshold)?;
+        
+        let council = &mut ctx.accounts.council;
+        council.treasury = ctx.accounts.treasury.key();
+        council.members = members;
+        council.threshold = threshold;
+        council.version = 0;
+        council.bump = ctx.bumps.council;
+        
+        emit!(CouncilInitialized {
+            council: council.key(),
+            treasury: council.treasury,
+            members: council.members.clone(),
+            threshold,
+        });
+        
+        Ok(())
+    }
+
+    /// Council member approves execution of a proposal
+    pub fn approve_execution(ctx: Context<ApproveExecution>) -> Result<()> {
+        let council = &ctx.accounts.council;
+        let approval = &mut ctx.accounts.execution_approval;
+        
+        require!(
+            !ctx.accounts.treasury.is_paused(PAUSE_EXECUTION),
+            TreasuryError::Paused
+        );
+        require!(!ctx.accounts.treasury.is_shut_down(), TreasuryError::TreasuryShutDown);
+        let index = council
+            .member_index(&ctx.accounts.member.key())
+            .ok_or(TreasuryError::NotCouncilMember)?;
+        
+        if approval.payer == Pubkey::default() {
+            approval.payer = ctx.accounts.member.key();
+        }
+        
+        // Approvals collected under a previous council membership no longer count
+        if approval.council != council.key() || approval.council_version != council.version {
+            approval.council = council.key();
+            approval.proposal = ctx.accounts.proposal.key();
+            approval.council_version = council.version;
+            approval.approval_bitmap = 0;
+            approval.approval_count = 0;
+        }
+        
+        let bit = 1u16 << index;
+        require!(
+            approval.approval_bitmap & bit == 0,
+            TreasuryError::AlreadyApproved
+        );
+        approval.approval_bitmap |= bit;
+        approval.approval_count = approval.approval_count.checked_add(1).unwrap();
+        
+        emit!(ExecutionApproved {
+            proposal: approval.proposal,
+            member: ctx.accounts.member.key(),
+            approval_count: approval.approval_count,
+            threshold: council.threshold,
+        });
+        
+        Ok(())
+    }
+
+    /// Council member proposes replacing the council membership and threshold
+    pub fn propose_council_rotation(
+        ctx: Context<ProposeCouncilRotation>,
+        new_members: Vec<Pubkey>,
+        new_threshold: u8,
+    ) -> Result<()> {
+        let council = &ctx.accounts.council;
+        let rotation = &mut ctx.accounts.rotation;
+        
+        let index = council
+            .member_index(&ctx.accounts.proposer.key())
+            .ok_or(TreasuryError::NotCouncilMember)?;
+        validate_council(&new_members, new_threshold)?;
+        
+        rotation.council = council.key();
+        rotation.proposer = ctx.accounts.proposer.key();
+        rotation.council_version = council.version;
+        rotation.new_members = new_members;
+        rotation.new_threshold = new_threshold;
+        rotation.approval_bitmap = 1u16 << index;
+        rotation.approval_count = 1;
+        
+        emit!(CouncilRotationProposed {
+            council: council.key(),
+            rotation: rotation.key(),
+            proposer: rotation.proposer,
+            new_members: rotation.new_members.clone(),
+            new_threshold,
+        });
+        
+        Ok(())
+    }
+
+    /// Council member approves a pending council rotation
+    pub fn approve_council_rotation(ctx: Context<ApproveCouncilRotation>) -> Result<()> {
+        let council = &ctx.accounts.council;
+        let rotation = &mut ctx.accounts.rotation;
+        
+        let index = council
+            .member_index(&ctx.accounts.member.key())
+            .ok_or(TreasuryError::NotCouncilMember)?;
+        require!(
+            rotation.council_version == council.version,
+            TreasuryError::StaleApproval
+        );
+        
+        let bit = 1u16 << index;
+        require!(
+            rotation.approval_bitmap & bit == 0,
+            TreasuryError::AlreadyApproved
+        );
+        rotation.approval_bitmap |= bit;
+        rotation.approval_count = rotation.approval_count.checked_add(1).unwrap();
+        
+        emit!(CouncilRotationApproved {
+            council: council.key(),
+            rotation: rotation.key(),
+            member: ctx.accounts.member.key(),
+            approval_count: rotation.approval_count,
+        });
+        
+        Ok(())
+    }
+
+    /// Apply a council rotation once it has reached the current threshold
+    pub fn execute_council_rotation(ctx: Context<ExecuteCouncilRotation>) -> Result<()> {
+        let council = &mut ctx.accounts.council;
+        let rotation = &ctx.accounts.rotation;
+        
+        require!(
+            council.is_member(&ctx.accounts.member.key()),
+            TreasuryError::NotCouncilMember
+        );
+        require!(
+            rotation.council_version == council.version,
+            TreasuryError::StaleApproval
+        );
+        require!(
+            rotation.approval_count >= council.threshold,
+            TreasuryError::InsufficientApprovals
+        );
+        
+        let old_members = std::mem::replace(&mut council.members, rotation.new_members.clone());
+        council.threshold = rotation.new_threshold;
+        council.version = council.version.checked_add(1).unwrap();
+        
+        emit!(CouncilRotated {
+            council: council.key(),
+            old_members,
+            new_members: council.members.clone(),
+            new_threshold: council.threshold,
+            version: council.version,
+        });
+        
+        Ok(())
+    }
+
+    /// Proposer withdraws a pending or stale council rotation and reclaims its rent
+    pub fn cancel_council_rotation(ctx: Context<CancelCouncilRotation>) -> Result<()> {
+        emit!(CouncilRotationCancelled {
+            council: ctx.accounts.council.key(),
+            rotation: ctx.accounts.rotation.key(),
+            proposer: ctx.accounts.proposer.key(),
+        });
+        
+        Ok(())
+    }
+
+    /// Finalize the vote: reject the proposal or queue it behind the execution timelock
+    pub fn queue_decision(ctx: Context<QueueDecision>) -> Result<()> {
+        let proposal = &mut ctx.accounts.proposal;
+        let treasury = &ctx.accounts.treasury;
+        let council = &ctx.accounts.council;
+        let approval = &ctx.accounts.execution_approval;
+        let clock = Clock::get()?;
+        
+        require!(!treasury.is_paused(PAUSE_EXECUTION), TreasuryError::Paused);
+        require!(!treasury.is_shut_down(), TreasuryError::TreasuryShutDown);
+        
+        // CRITICAL: Verify executor sits on the treasury council
+        require!(
+            council.is_member(&ctx.accounts.executor.key()),
+            TreasuryError::UnauthorizedExecutor
+        );
+        
+        require!(
+            clock.unix_timestamp >= proposal.voting_ends_at,
+            TreasuryError::VotingPeriodActive
+        );
+        require!(
+            matches!(proposal.status, ProposalStatus::Voting),
+            TreasuryError::InvalidProposalStatus
+        );
+        
+        if let Some(reason) = decision_rejection(treasury, proposal) {
+            proposal.status = ProposalStatus::Rejected;
+            proposal.finalized_at = clock.unix_timestamp;
+            
+            emit!(DecisionRejected {
+                proposal: proposal.key(),
+                votes_for: proposal.votes_for,
+                votes_against: proposal.votes_against,
+                quorum_threshold: treasury.params.quorum_threshold,
+                reason,
+            });
+            
+            return Ok(());
+        }
+        
+        let approval = approval
+            .as_ref()
+            .ok_or(TreasuryError::InsufficientApprovals)?;
+        require!(
+            approval.council_version == council.version,
+            TreasuryError::StaleApproval
+        );
+        require!(
+            approval.approval_count >= council.threshold,
+            TreasuryError::InsufficientApprovals
+        );
+        
+        proposal.status = ProposalStatus::Queued;
+        proposal.eta = clock
+            .unix_timestamp
+            .checked_add(treasury.params.execution_delay)
+            .ok_or(TreasuryError::CalculationOverflow)?;
+        
+        emit!(DecisionQueued {
+            proposal: proposal.key(),
+            eta: proposal.eta,
+            expires_at: proposal.eta.saturating_add(treasury.params.execution_grace_period),
+        });
+        
+        Ok(())
+    }
+
+    /// Execute a queued decision inside its execution window
+    /// Remaining accounts: the source adapter layout (rebalance only) then the target adapter layout
+    pub fn execute_queued<'info>(
+        ctx: Context<'_, '_, 'info, 'info, ExecuteQueued<'info>>,
+    ) -> Result<()> {
+        let treasury = &ctx.accounts.treasury;
+        let proposal = &ctx.accounts.proposal;
+        let clock = Clock::get()?;
+        
+        require!(!treasury.is_paused(PAUSE_EXECUTION), TreasuryError::Paused);
+        require!(!treasury.is_shut_down(), TreasuryError::TreasuryShutDown);
+        
+        // CRITICAL: Verify executor sits on the treasury council
+        require!(
+            ctx.accounts.council.is_member(&ctx.accounts.executor.key()),
+            TreasuryError::UnauthorizedExecutor
+        );
+        
+        require!(
+            matches!(proposal.status, ProposalStatus::Queued),
+            TreasuryError::InvalidProposalStatus
+        );
+        require!(
+            clock.unix_timestamp >= proposal.eta,
+            TreasuryError::TimelockActive
+        );
+        require!(
+            clock.unix_timestamp <= proposal.eta.saturating_add(treasury.params.execution_grace_period),
+            TreasuryError::ExecutionWindowClosed
+        );
+        
+        // Risk budget is charged on execution so rejected proposals cost nothing
+        let epoch_risk_used = treasury
+            .epoch_risk_used
+            .checked_add(proposal.decision.risk_score as u64)
+            .unwrap();
+        require!(
+            epoch_risk_used <= treasury.params.epoch_risk_budget,
+            TreasuryError::EpochRiskBudgetExceeded
+        );
+        
+        // Move vault funds through the strategy adapter; any CPI failure reverts execution
+        execute_strategy(ctx.accounts, ctx.remaining_accounts)?;
+        
+        let proposal = &mut ctx.accounts.proposal;
+        let treasury = &mut ctx.accounts.treasury;
+        treasury.epoch_risk_used = epoch_risk_used;
+        proposal.status = ProposalStatus::Executed;
+        proposal.finalized_at = clock.unix_timestamp;
+        treasury.decision_count = treasury.decision_count.checked_add(1).unwrap();
+        treasury.epoch_decisions_executed = treasury
+            .epoch_decisions_executed
+            .checked_add(1)
+            .unwrap();
+        treasury.last_rebalance = clock.unix_timestamp;
+        
+        emit!(DecisionExecuted {
+            proposal: proposal.key(),
+            decision: proposal.decision.clone(),
+            execution_time: clock.unix_timestamp,
+        });
+        
+        Ok(())
+    }
+
+    /// Council member or guardian vetoes a queued decision before its timelock elapses
+    pub fn veto_decision(ctx: Context<VetoDecision>) -> Result<()> {
+        let proposal = &mut ctx.accounts.proposal;
+        let signer = ctx.accounts.signer.key();
+        let now = Clock::get()?.unix_timestamp;
+        
+        require!(
+            ctx.accounts.council.is_member(&signer) || signer == ctx.accounts.treasury.guardian,
+            TreasuryError::UnauthorizedVeto
+        );
+        require!(
+            matches!(proposal.status, ProposalStatus::Queued),
+            TreasuryError::InvalidProposalStatus
+        );
+        require!(now < proposal.eta, TreasuryError::TimelockElapsed);
+        
+        proposal.status = ProposalStatus::Vetoed;
+        proposal.finalized_at = now;
+        
+        emit!(DecisionVetoed {
+            proposal: proposal.key(),
+            vetoed_by: signer,
+        });
+        
+        Ok(())
+    }
+
+    /// Mark a queued decision expired once its execution window has passed
+    /// Permissionless so stale queue entries can always be cleared
+    pub fn expire_decision(ctx: Context<ExpireDecision>) -> Result<()> {
+        let proposal = &mut ctx.accounts.proposal;
+        let now = Clock::get()?.unix_timestamp;
+        
+        require!(
+            matches!(proposal.status, ProposalStatus::Queued),
+            TreasuryError::InvalidProposalStatus
+        );
+        require!(
+            now > proposal
+                .eta
+                .saturating_add(ctx.accounts.treasury.params.execution_grace_period),
+            TreasuryError::ExecutionWindowOpen
+        );
+        
+        proposal.status = ProposalStatus::Expired;
+        proposal.finalized_at = now;
+        
+        emit!(DecisionExpired {
+            proposal: proposal.key(),
+            expired_at: now,
+        });
+        
+        Ok(())
+    }
+
+    /// Permanently freeze proposals, voting and execution and open pro-rata exits
+    /// SECURITY: Authority only; the guardian can pause but not make that permanent,
+    /// and there is deliberately no way back
+    pub fn emergency_shutdown(ctx: Context<EmergencyShutdown>) -> Result<()> {
+        let treasury = &mut ctx.accounts.treasury;
+        let authority = ctx.accounts.authority.key();
+        
+        require!(authority == treasury.authority, TreasuryError::Unauthorized);
+        require!(!treasury.is_shut_down(), TreasuryError::TreasuryShutDown);
+        
+        treasury.shutdown_at = Clock::get()?.unix_timestamp;
+        
+        emit!(TreasuryShutDown {
+            treasury: treasury.key(),
+            triggered_by: authority,
+            shutdown_at: treasury.shutdown_at,
+        });
+        
+        Ok(())
+    }
+
+    /// Pull `amount` back from a position through its strategy adapter after shutdown
+    /// Permissionless and repeatable; funds only ever move from the protocol into the vault
+    /// Remaining accounts: the adapter's registered account layout
+    pub fn force_unwind<'info>(
+        ctx: Context<'_, '_, 'info, 'info, ForceUnwind<'info>>,
+        amount: u64,
+    ) -> Result<()> {
+        require!(ctx.accounts.treasury.is_shut_down(), TreasuryError::NotShutDown);
+        require!(amount > 0, TreasuryError::ZeroAmount);
+        
+        let accounts = ctx.accounts;
+        let (before, after) = invoke_adapter(
+            &accounts.adapter_vault(),
+            &accounts.strategy_adapter,
+            &accounts.adapter_program,
+            ctx.remaining_accounts,
+            ADAPTER_WITHDRAW_DISCRIMINATOR,
+            amount,
+        )?;
+        let received = after.saturating_sub(before);
+        
+        record_recall(
+            &mut accounts.treasury,
+            &mut accounts.vault_asset,
+            &mut accounts.position,
+            received,
+        )?;
+        let inflow = i64::try_from(received).map_err(|_| TreasuryError::CalculationOverflow)?;
+        apply_net_outflow(
+            &accounts.treasury,
+            &mut accounts.vault_asset,
+            -inflow,
+            Clock::get()?.unix_timestamp,
+        )?;
+        
+        emit!(PositionUnwound {
+            position: accounts.position.key(),
+            received,
+            remaining_principal: accounts.position.principal,
+            remaining_value: accounts.position.marked_value,
+            cranked_by: accounts.cranker.key(),
+        });
+        
+        Ok(())
+    }
+
+    /// Redeem shares for their pro-rata slice of an asset's total assets after shutdown
+    /// Paid from idle balance; when idle cannot cover the claim, only the shares backing
+    /// the amount paid are burned and the rest stay redeemable once force_unwind recalls more
+    /// Ignores pause flags, outflow caps and the asset whitelist so no one can hold exits up
+    pub fn emergency_withdraw(ctx: Context<EmergencyWithdraw>, shares: u64) -> Result<()> {
+        require!(ctx.accounts.treasury.is_shut_down(), TreasuryError::NotShutDown);
+        require!(shares > 0, TreasuryError::ZeroShares);
+        
+        let (amount, shares_burned) = emergency_redemption(
+            shares,
+            ctx.accounts.vault_asset.balance,
+            ctx.accounts.vault_asset.total_assets(),
+            ctx.accounts.share_mint.supply,
+        )
+        .ok_or(TreasuryError::CalculationOverflow)?;
+        require!(shares_burned > 0, TreasuryError::InsufficientIdleBalance);
+        
+        let cpi_accounts = Burn {
+            mint: ctx.accounts.share_mint.to_account_info(),
+            from: ctx.accounts.user_share_account.to_account_info(),
+            authority: ctx.accounts.user.to_account_info(),
+        };
+        let cpi_program = ctx.accounts.token_program.to_account_info();
+        let cpi_ctx = CpiContext::new(cpi_program, cpi_accounts);
+        
+        token::burn(cpi_ctx, shares_burned)?;
+        
+        if amount > 0 {
+            let treasury_key = ctx.accounts.treasury.key();
+            let signer_seeds: &[&[&[u8]]] = &[&[
+                b"vault_authority",
+                treasury_key.as_ref(),
+                &[ctx.accounts.treasury.vault_authority_bump],
+            ]];
+            let cpi_accounts = Transfer {
+                from: ctx.accounts.treasury_token_account.to_account_info(),
+                to: ctx.accounts.user_token_account.to_account_info(),
+                authority: ctx.accounts.vault_authority.to_account_info(),
+            };
+            let cpi_program = ctx.accounts.token_program.to_account_info();
+            let cpi_ctx = CpiContext::new_with_signer(cpi_program, cpi_accounts, signer_seeds);
+            
+            token::transfer(cpi_ctx, amount)?;
+        }
+        
+        let vault_asset = &mut ctx.accounts.vault_asset;
+        vault_asset.balance = vault_asset.balance.checked_sub(amount).unwrap();
+        
+        let treasury = &mut ctx.accounts.treasury;
+        treasury.total_value_locked = treasury.total_value_locked.checked_sub(amount).unwrap();
+        
+        emit!(EmergencyWithdrawal {
+            treasury: treasury.key(),
+            user: ctx.accounts.user.key(),
+            mint: vault_asset.mint,
+            shares_burned,
+            amount,
+            remaining_deployed: vault_asset.deployed,
+        });
+        
+        Ok(())
+    }
+
+    /// Crystallize management and performance fees for one asset
+    /// Permissionless so fees accrue even while an asset sees no deposits or withdrawals
+    pub fn accrue_fees(ctx: Context<AccrueFees>) -> Result<()> {
+        // Fee shares dilute holders, so they are not minted while issuance or redemption is paused
+        require!(
+            !ctx.accounts.treasury.is_paused(PAUSE_DEPOSITS | PAUSE_WITHDRAWALS),
+            TreasuryError::Paused
+        );
+        // No fees are charged while depositors wind down
+        require!(!ctx.accounts.treasury.is_shut_down(), TreasuryError::TreasuryShutDown);
+        
+        accrue_asset_fees(
+            &ctx.accounts.treasury,
+            &mut ctx.accounts.vault_asset,
+            &mut ctx.accounts.share_mint,
+            &ctx.accounts.fee_share_account,
+            &ctx.accounts.vault_authority,
+            &ctx.accounts.token_program,
+        )
+    }
+
+    /// Close the current epoch into an immutable snapshot and reset per-epoch counters
+    /// Remaining accounts: one (VaultAsset, share Mint) pair per whitelisted asset
+    /// Not pause-gated: it moves no funds, and epochs must keep rolling while paused
+    /// so risk budgets and snapshots stay on schedule
+    pub fn advance_epoch<'info>(
+        ctx: Context<'_, '_, 'info, 'info, AdvanceEpoch<'info>>,
+    ) -> Result<()> {
+        let treasury = &mut ctx.accounts.treasury;
+        let snapshot = &mut ctx.accounts.epoch_snapshot;
+        let now = Clock::get()?.unix_timestamp;
+        
+        let epoch_ends_at = treasury
+            .epoch_started_at
+            .checked_add(treasury.params.epoch_duration)
+            .ok_or(TreasuryError::CalculationOverflow)?;
+        require!(now >= epoch_ends_at, TreasuryError::EpochNotEnded);
+        require!(
+            ctx.remaining_accounts.len() == treasury.asset_count as usize * 2,
+            TreasuryError::InvalidAssetAccounts
+        );
+        
+        let mut assets: Vec<AssetSnapshot> = Vec::with_capacity(treasury.asset_count as usize);
+        for pair in ctx.remaining_accounts.chunks(2) {
+            // Rolled in memory only, so counters left from an earlier epoch read as zero
+            let mut vault_asset = Account::<VaultAsset>::try_from(&pair[0])?;
+            vault_asset.roll_epoch(treasury.current_epoch);
+            let share_mint = Account::<Mint>::try_from(&pair[1])?;
+            require!(
+                vault_asset.treasury == treasury.key()
+                    && share_mint.key() == vault_asset.share_mint
+                    && !assets.iter().any(|asset| asset.mint == vault_asset.mint),
+                TreasuryError::InvalidAssetAccounts
+            );
+            
+            assets.push(AssetSnapshot {
+                mint: vault_asset.mint,
+                balance: vault_asset.total_assets(),
+                share_supply: share_mint.supply,
+                share_price: share_price(vault_asset.total_assets(), share_mint.supply)
+                    .ok_or(TreasuryError::CalculationOverflow)?,
+                realized_yield: vault_asset.epoch_realized_yield,
+                fees: vault_asset.epoch_fees,
+            });
+        }
+        
+        snapshot.treasury = treasury.key();
+        snapshot.epoch = treasury.current_epoch;
+        snapshot.started_at = treasury.epoch_started_at;
+        snapshot.ended_at = now;
+        snapshot.decisions_executed = treasury.epoch_decisions_executed;
+        snapshot.risk_used = treasury.epoch_risk_used;
+        snapshot.assets = assets;
+        
+        treasury.current_epoch = treasury.current_epoch.checked_add(1).unwrap();
+        treasury.epoch_started_at = now;
+        treasury.epoch_risk_used = 0;
+        treasury.epoch_decisions_executed = 0;
+        
+        emit!(EpochAdvanced {
+            treasury: treasury.key(),
+            snapshot: snapshot.key(),
+            closed_epoch: snapshot.epoch,
+            new_epoch: treasury.current_epoch,
+            decisions_executed: snapshot.decisions_executed,
+        });
+        
+        Ok(())
+    }
+
+    /// Cancel a proposal that is still in its voting period
+    /// SECURITY: Proposer may cancel only before any votes; authority may cancel any time
+    pub fn cancel_proposal(ctx: Context<CancelProposal>) -> Result<()> {
+        let proposal = &mut ctx.accounts.proposal;
+        let signer = ctx.accounts.signer.key();
+        
+        require!(
+            matches!(proposal.status, ProposalStatus::Voting),
+            TreasuryError::InvalidProposalStatus
+        );
+        
+        let by_authority = signer == ctx.accounts.treasury.authority;
+        if !by_authority {
+            require!(signer == proposal.proposer, TreasuryError::Unauthorized);
+            require!(
+                proposal.votes_for == 0 && proposal.votes_against == 0,
+                TreasuryError::ProposalHasVotes
+            );
+        }
+        
+        proposal.status = ProposalStatus::Cancelled;
+        proposal.finalized_at = Clock::get()?.unix_timestamp;
+        
+        emit!(ProposalCancelled {
+            proposal: proposal.key(),
+            cancelled_by: signer,
+            by_authority,
+        });
+        
+        Ok(())
+    }
+
+    /// Close a finalized proposal after the retention window, returning rent to the proposer
+    pub fn close_proposal(ctx: Context<CloseProposal>) -> Result<()> {
+        let proposal = &ctx.accounts.proposal;
+        
+        require!(proposal.status.is_final(), TreasuryError::InvalidProposalStatus);
+        let closable_at = proposal
+            .finalized_at
+            .checked_add(ctx.accounts.treasury.params.proposal_retention)
+            .ok_or(TreasuryError::CalculationOverflow)?;
+        require!(
+            Clock::get()?.unix_timestamp >= closable_at,
+            TreasuryError::RetentionPeriodActive
+        );
+        
+        emit!(ProposalClosed {
+            proposal: proposal.key(),
+            proposer: proposal.proposer,
+            rent_returned: proposal.to_account_info().lamports(),
+        });
+        
+        Ok(())
+    }
+
+    /// Close a vote record once its proposal is final (or already closed), returning rent to the voter
+    pub fn close_vote_record(ctx: Context<CloseVoteRecord>) -> Result<()> {
+        require!(
+            proposal_settled(&ctx.accounts.proposal)?,
+            TreasuryError::InvalidProposalStatus
+        );
+        
+        emit!(VoteRecordClosed {
+            vote: ctx.accounts.vote.key(),
+            proposal: ctx.accounts.vote.proposal,
+            voter: ctx.accounts.voter.key(),
+        });
+        
+        Ok(())
+    }
+
+    /// Close an execution approval once its proposal is final (or already closed),
+    /// returning rent to the council member who created it
+    pub fn close_execution_approval(ctx: Context<CloseExecutionApproval>) -> Result<()> {
+        require!(
+            proposal_settled(&ctx.accounts.proposal)?,
+            TreasuryError::InvalidProposalStatus
+        );
+        
+        emit!(ExecutionApprovalClosed {
+            approval: ctx.accounts.execution_approval.key(),
+            proposal: ctx.accounts.execution_approval.proposal,
+            payer: ctx.accounts.payer.key(),
+        });
+        
+        Ok(())
+    }
+
+    /// Replace the set of oracles allowed to record outcomes
+    /// SECURITY: Only treasury authority can change the oracle set
+    pub fn set_oracles(ctx: Context<SetOracles>, oracles: Vec<Pubkey>) -> Result<()> {
+        let treasury = &ctx.accounts.treasury;
+        let oracle_set = &mut ctx.accounts.oracle_set;
+        
+        require!(
+            ctx.accounts.authority.key() == treasury.authority,
+            TreasuryError::Unauthorized
+        );
+        require!(oracles.len() <= MAX_ORACLES, TreasuryError::TooManyOracles);
+        
+        oracle_set.treasury = treasury.key();
+        oracle_set.oracles = oracles;
+        oracle_set.bump = ctx.bumps.oracle_set;
+        
+        emit!(OraclesUpdated {
+            treasury: treasury.key(),
+            oracles: oracle_set.oracles.clone(),
+        });
+        
+        Ok(())
+    }
+
+    /// Record the result of an executed decision for learning
+    /// SECURITY: Only the authority or a designated oracle may record, and only once
+    pub fn record_outcome(
+        ctx: Context<RecordOutcome>,
+        actual_yield_bps: u16,
+        success: bool,
+    ) -> Result<()> {
+        let treasury = &ctx.accounts.treasury;
+        let proposal = &mut ctx.accounts.proposal;
+        let recorder = ctx.accounts.recorder.key();
+        
+        require!(!treasury.is_paused(PAUSE_OUTCOMES), TreasuryError::Paused);
+        require!(
+            recorder == treasury.authority
+                || ctx
+                    .accounts
+                    .oracle_set
+                    .as_ref()
+                    .is_some_and(|oracle_set| oracle_set.oracles.contains(&recorder)),
+            TreasuryError::UnauthorizedRecorder
+        );
+        require!(
+            matches!(proposal.status, ProposalStatus::Executed),
+            TreasuryError::DecisionNotExecuted
+        );
+        require!(
+            proposal.outcome.is_none(),
+            TreasuryError::OutcomeAlreadyRecorded
+        );
+        
+        proposal.outcome = Some(Outcome {
+            actual_yield_bps,
+            success,
+            recorded_at: Clock::get()?.unix_timestamp,
+        });
+        
+        // Feed prediction accuracy back into the proposing agent's reputation
+        let delta = reputation_delta(
+            proposal.decision.expected_yield_bps,
+            actual_yield_bps,
+            success,
+        );
+        let treasury_key = treasury.key();
+        let signer_seeds: &[&[&[u8]]] = &[&[
+            b"reputation_authority",
+            treasury_key.as_ref(),
+            &[ctx.bumps.reputation_authority],
+        ]];
+        let cpi_accounts = agent_registry::cpi::accounts::AdjustReputation {
+            registry: ctx.accounts.registry.to_account_info(),
+            agent: ctx.accounts.agent.to_account_info(),
+            adjuster_record: ctx.accounts.adjuster_record.to_account_info(),
+            adjuster: ctx.accounts.reputation_authority.to_account_info(),
+        };
+        let cpi_program = ctx.accounts.agent_registry_program.to_account_info();
+        let cpi_ctx = CpiContext::new_with_signer(cpi_program, cpi_accounts, signer_seeds);
+        
+        agent_registry::cpi::adjust_reputation(cpi_ctx, delta)?;
+        
+        emit!(OutcomeRecorded {
+            proposal: proposal.key(),
+            agent: proposal.decision.agent_id,
+            predicted_yield_bps: proposal.decision.expected_yield_bps,
+            actual_yield_bps,
+            success,
+            recorder,
+            reputation_delta: delta,
+        });
+        
+        Ok(())
+    }
+}
+
+#[derive(Accounts)]
+pub struct Initialize<'info> {
+    #[account(init, payer = authority, space = Treasury::SIZE)]
+    pub treasury: Account<'info, Treasury>,
+    pub registry: Account<'info, Registry>,
+    #[account(mut)]
+    pub authority: Signer<'info>,
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+pub struct ProposeAuthorityTransfer<'info> {
+    #[account(mut)]
+    pub treasury: Account<'info, Treasury>,
+    pub authority: Signer<'info>,
+}
+
+#[derive(Accounts)]
+pub struct AcceptAuthorityTransfer<'info> {
+    #[account(mut)]
+    pub treasury: Account<'info, Treasury>,
+    pub new_authority: Signer<'info>,
+}
+
+#[derive(Accounts)]
+pub struct CancelAuthorityTransfer<'info> {
+    #[account(mut)]
+    pub treasury: Account<'info, Treasury>,
+    pub authority: Signer<'info>,
+}
+
+#[derive(Accounts)]
+pub struct SetFeeRecipient<'info> {
+    #[account(mut)]
+    pub treasury: Account<'info, Treasury>,
+    pub authority: Signer<'info>,
+}
+
+#[derive(Accounts)]
+pub struct ProposeParamsUpdate<'info> {
+    pub treasury: Account<'info, Treasury>,
+    #[account(
+        init,
+        payer = authority,
+        space = ParamsChange::SIZE,
+        seeds = [b"params_change", treasury.key().as_ref()],
+        bump
+    )]
+    pub params_change: Account<'info, ParamsChange>,
+    pub registry: Account<'info, Registry>,
+    #[account(mut)]
+    pub authority: Signer<'info>,
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+pub struct UpdateParams<'info> {
+    #[account(mut)]
+    pub treasury: Account<'info, Treasury>,
+    #[account(
+        mut,
+        close = proposer,
+        seeds = [b"params_change", treasury.key().as_ref()],
+        bump = params_change.bump
+    )]
+    pub params_change: Account<'info, ParamsChange>,
+    /// CHECK: Receives the change rent; bound to the recorded proposer
+    #[account(mut, address = params_change.proposer)]
+    pub proposer: UncheckedAccount<'info>,
+    pub authority: Signer<'info>,
+}
+
+#[derive(Accounts)]
+pub struct CancelParamsUpdate<'info> {
+    pub treasury: Account<'info, Treasury>,
+    #[account(
+        mut,
+        close = proposer,
+        seeds = [b"params_change", treasury.key().as_ref()],
+        bump = params_change.bump
+    )]
+    pub params_change: Account<'info, ParamsChange>,
+    /// CHECK: Receives the change rent; bound to the recorded proposer
+    #[account(mut, address = params_change.proposer)]
+    pub proposer: UncheckedAccount<'info>,
+    #[account(
+        seeds = [b"council", treasury.key().as_ref()],
+        bump = council.bump
+    )]
+    pub council: Option<Account<'info, Council>>,
+    pub signer: Signer<'info>,
+}
+
+#[derive(Accounts)]
+pub struct SetGuardian<'info> {
+    #[account(mut)]
+    pub treasury: Account<'info, Treasury>,
+    pub authority: Signer<'info>,
+}
+
+#[derive(Accounts)]
+pub struct SetPaused<'info> {
+    #[account(mut)]
+    pub treasury: Account<'info, Treasury>,
+    pub signer: Signer<'info>,
+}
+
+#[derive(Accounts)]
+pub struct WhitelistAsset<'info> {
+    #[account(mut)]
+    pub treasury: Account<'info, Treasury>,
+    #[account(constraint = asset_mint.decimals <= MAX_ASSET_DECIMALS @ TreasuryError::UnsupportedDecimals)]
+    pub asset_mint: Account<'info, Mint>,
+    #[account(
+        init_if_needed,
+        payer = authority,
+        space = VaultAsset::SIZE,
+        seeds = [b"vault_asset", treasury.key().as_ref(), asset_mint.key().as_ref()],
+        bump
+    )]
+    pub vault_asset: Account<'info, VaultAsset>,
+    /// CHECK: PDA used only as mint and vault authority
+    #[account(seeds = [b"vault_authority", treasury.key().as_ref()], bump)]
+    pub vault_authority: UncheckedAccount<'info>,
+    #[account(
+        init_if_needed,
+        payer = authority,
+        token::mint = asset_mint,
+        token::authority = vault_authority,
+        seeds = [b"vault", treasury.key().as_ref(), asset_mint.key().as_ref()],
+        bump
+    )]
+    pub vault: Account<'info, TokenAccount>,
+    #[account(
+        init_if_needed,
+        payer = authority,
+        mint::decimals = asset_mint.decimals + SHARE_DECIMALS_OFFSET,
+        mint::authority = vault_authority,
+        seeds = [b"share_mint", treasury.key().as_ref(), asset_mint.key().as_ref()],
+        bump
+    )]
+    pub share_mint: Account<'info, Mint>,
+    #[account(mut)]
+    pub authority: Signer<'info>,
+    pub token_program: Program<'info, Token>,
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+#[instruction(target_protocol: String)]
+pub struct RegisterStrategyAdapter<'info> {
+    pub treasury: Account<'info, Treasury>,
+    pub asset_mint: Account<'info, Mint>,
+    #[account(
+        init_if_needed,
+        payer = authority,
+        space = StrategyAdapter::SIZE,
+        seeds = [
+            b"strategy_adapter",
+            treasury.key().as_ref(),
+            asset_mint.key().as_ref(),
+            target_protocol.as_bytes()
+        ],
+        bump
+    )]
+    pub strategy_adapter: Account<'info, StrategyAdapter>,
+    #[account(
+        init_if_needed,
+        payer = authority,
+        space = Position::SIZE,
+        seeds = [
+            b"position",
+            treasury.key().as_ref(),
+            asset_mint.key().as_ref(),
+            target_protocol.as_bytes()
+        ],
+        bump
+    )]
+    pub position: Account<'info, Position>,
+    #[account(mut)]
+    pub authority: Signer<'info>,
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+pub struct MarkPosition<'info> {
+    #[account(mut)]
+    pub treasury: Account<'info, Treasury>,
+    #[account(mut, has_one = treasury)]
+    pub position: Account<'info, Position>,
+    #[account(
+        mut,
+        seeds = [b"vault_asset", treasury.key().as_ref(), position.mint.as_ref()],
+        bump = vault_asset.bump
+    )]
+    pub vault_asset: Account<'info, VaultAsset>,
+    #[account(
+        seeds = [b"oracle_set", treasury.key().as_ref()],
+        bump = oracle_set.bump
+    )]
+    pub oracle_set: Option<Account<'info, OracleSet>>,
+    pub recorder: Signer<'info>,
+}
+
+#[derive(Accounts)]
+pub struct DisableStrategyAdapter<'info> {
+    pub treasury: Account<'info, Treasury>,
+    #[account(mut, has_one = treasury)]
+    pub strategy_adapter: Account<'info, StrategyAdapter>,
+    pub authority: Signer<'info>,
+}
+
+#[derive(Accounts)]
+pub struct DelistAsset<'info> {
+    pub treasury: Account<'info, Treasury>,
+    #[account(
+        mut,
+        seeds = [b"vault_asset", treasury.key().as_ref(), vault_asset.mint.as_ref()],
+        bump = vault_asset.bump
+    )]
+    pub vault_asset: Account<'info, VaultAsset>,
+    pub authority: Signer<'info>,
+}
+
+#[derive(Accounts)]
+pub struct Deposit<'info> {
+    #[account(mut)]
+    pub treasury: Account<'info, Treasury>,
+    #[account(
+        mut,
+        seeds = [b"vault_asset", treasury.key().as_ref(), vault_asset.mint.as_ref()],
+        bump = vault_asset.bump
+    )]
+    pub vault_asset: Account<'info, VaultAsset>,
+    pub user: Signer<'info>,
+    #[account(mut, token::mint = vault_asset.mint)]
+    pub user_token_account: Account<'info, TokenAccount>,
+    #[account(
+        mut,
+        address = vault_asset.vault @ TreasuryError::InvalidVault,
+        token::mint = vault_asset.mint,
+        token::authority = vault_authority
+    )]
+    pub treasury_token_account: Account<'info, TokenAccount>,
+    /// CHECK: PDA used only as mint and vault authority
+    #[account(
+        seeds = [b"vault_authority", treasury.key().as_ref()],
+        bump = treasury.vault_authority_bump
+    )]
+    pub vault_authority: UncheckedAccount<'info>,
+    #[account(mut, address = vault_asset.share_mint)]
+    pub share_mint: Account<'info, Mint>,
+    #[account(mut, token::mint = share_mint)]
+    pub user_share_account: Account<'info, TokenAccount>,
+    #[account(
+        mut,
+        token::mint = share_mint,
+        constraint = fee_share_account.owner == treasury.fee_recipient @ TreasuryError::InvalidFeeAccount
+    )]
+    pub fee_share_account: Account<'info, TokenAccount>,
+    pub token_program: Program<'info, Token>,
+}
+
+#[derive(Accounts)]
+pub struct Withdraw<'info> {
+    #[account(mut)]
+    pub treasury: Account<'info, Treasury>,
+    #[account(
+        mut,
+        seeds = [b"vault_asset", treasury.key().as_ref(), vault_asset.mint.as_ref()],
+        bump = vault_asset.bump
+    )]
+    pub vault_asset: Account<'info, VaultAsset>,
+    pub user: Signer<'info>,
+    #[account(mut, token::mint = vault_asset.mint)]
+    pub user_token_account: Account<'info, TokenAccount>,
+    #[account(
+        mut,
+        address = vault_asset.vault @ TreasuryError::InvalidVault,
+        token::mint = vault_asset.mint,
+        token::authority = vault_authority
+    )]
+    pub treasury_token_account: Account<'info, TokenAccount>,
+    /// CHECK: PDA used only as mint and vault authority
+    #[account(
+        seeds = [b"vault_authority", treasury.key().as_ref()],
+        bump = treasury.vault_authority_bump
+    )]
+    pub vault_authority: UncheckedAccount<'info>,
+    #[account(mut, address = vault_asset.share_mint)]
+    pub share_mint: Account<'info, Mint>,
+    #[account(mut, token::mint = share_mint, token::authority = user)]
+    pub user_share_account: Account<'info, TokenAccount>,
+    #[account(
+        mut,
+        token::mint = share_mint,
+        constraint = fee_share_account.owner == treasury.fee_recipient @ TreasuryError::InvalidFeeAccount
+    )]
+    pub fee_share_account: Account<'info, TokenAccount>,
+    pub token_program: Program<'info, Token>,
+}
+
+#[derive(Accounts)]
+pub struct AccrueFees<'info> {
+    pub treasury: Account<'info, Treasury>,
+    #[account(
+        mut,
+        seeds = [b"vault_asset", treasury.key().as_ref(), vault_asset.mint.as_ref()],
+        bump = vault_asset.bump
+    )]
+    pub vault_asset: Account<'info, VaultAsset>,
+    /// CHECK: PDA used only as mint and vault authority
+    #[account(
+        seeds = [b"vault_authority", treasury.key().as_ref()],
+        bump = treasury.vault_authority_bump
+    )]
+    pub vault_authority: UncheckedAccount<'info>,
+    #[account(mut, address = vault_asset.share_mint)]
+    pub share_mint: Account<'info, Mint>,
+    #[account(
+        mut,
+        token::mint = share_mint,
+        constraint = fee_share_account.owner == treasury.fee_recipient @ TreasuryError::InvalidFeeAccount
+    )]
+    pub fee_share_account: Account<'info, TokenAccount>,
+    pub token_program: Program<'info, Token>,
+}
+
+#[derive(Accounts)]
+#[instruction(decision: YieldDecision)]
+pub struct SubmitDecision<'info> {
+    #[account(mut)]
+    pub treasury: Account<'info, Treasury>,
+    #[account(
+        seeds = [b"vault_asset", treasury.key().as_ref(), decision.asset_mint.as_ref()],
+        bump = vault_asset.bump
+    )]
+    pub vault_asset: Account<'info, VaultAsset>,
+    #[account(
+        init_if_needed,
+        payer = agent,
+        space = ProposalCounter::SIZE,
+        seeds = [b"proposal_counter", treasury.key().as_ref(), agent.key().as_ref()],
+        bump
+    )]
+    pub proposal_counter: Account<'info, ProposalCounter>,
+    #[account(
+        init,
+        payer = agent,
+        space = Proposal::SIZE,
+        seeds = [
+            b"proposal",
+            treasury.key().as_ref(),
+            agent.key().as_ref(),
+            &proposal_counter.next_sequence.to_le_bytes()
+        ],
+        bump
+    )]
+    pub proposal: Account<'info, Proposal>,
+    #[account(constraint = submitter_agent.registry == treasury.registry @ TreasuryError::WrongRegistry)]
+    pub submitter_agent: Account<'info, Agent>,
+    #[account(mut)]
+    pub agent: Signer<'info>,
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+pub struct RevealReasoning<'info> {
+    pub treasury: Account<'info, Treasury>,
+    #[account(mut, has_one = treasury)]
+    pub proposal: Account<'info, Proposal>,
+    #[account(address = proposal.proposer @ TreasuryError::Unauthorized)]
+    pub proposer: Signer<'info>,
+}
+
+#[derive(Accounts)]
+pub struct CastVote<'info> {
+    pub treasury: Account<'info, Treasury>,
+    #[account(mut, has_one = treasury)]
+    pub proposal: Account<'info, Proposal>,
+    #[account(
+        init,
+        payer = voter,
+        space = VoteRecord::SIZE,
+        seeds = [b"vote", proposal.key().as_ref(), voter.key().as_ref()],
+        bump
+    )]
+    pub vote: Account<'info, VoteRecord>,
+    #[account(constraint = voter_agent.registry == treasury.registry @ TreasuryError::WrongRegistry)]
+    pub voter_agent: Account<'info, Agent>,
+    #[account(mut)]
+    pub voter: Signer<'info>,
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+pub struct QueueDecision<'info> {
+    pub treasury: Account<'info, Treasury>,
+    #[account(mut, has_one = treasury)]
+    pub proposal: Account<'info, Proposal>,
+    #[account(
+        seeds = [b"council", treasury.key().as_ref()],
+        bump = council.bump
+    )]
+    pub council: Account<'info, Council>,
+    #[account(
+        seeds = [b"execution_approval", council.key().as_ref(), proposal.key().as_ref()],
+        bump
+    )]
+    pub execution_approval: Option<Account<'info, ExecutionApproval>>,
+    pub executor: Signer<'info>,
+}
+
+#[derive(Accounts)]
+pub struct ExecuteQueued<'info> {
+    #[account(mut)]
+    pub treasury: Account<'info, Treasury>,
+    #[account(mut, has_one = treasury)]
+    pub proposal: Account<'info, Proposal>,
+    #[account(
+        seeds = [b"council", treasury.key().as_ref()],
+        bump = council.bump
+    )]
+    pub council: Account<'info, Council>,
+    pub executor: Signer<'info>,
+    #[account(
+        mut,
+        seeds = [b"vault_asset", treasury.key().as_ref(), proposal.decision.asset_mint.as_ref()],
+        bump = vault_asset.bump
+    )]
+    pub vault_asset: Account<'info, VaultAsset>,
+    #[account(mut, address = vault_asset.vault @ TreasuryError::InvalidVault)]
+    pub vault: Account<'info, TokenAccount>,
+    /// CHECK: PDA used only as vault authority
+    #[account(
+        seeds = [b"vault_authority", treasury.key().as_ref()],
+        bump = treasury.vault_authority_bump
+    )]
+    pub vault_authority: UncheckedAccount<'info>,
+    pub strategy_adapter: Option<Account<'info, StrategyAdapter>>,
+    #[account(mut)]
+    pub position: Option<Account<'info, Position>>,
+    /// CHECK: Must match strategy_adapter.program_id, checked before the CPI
+    pub adapter_program: Option<UncheckedAccount<'info>>,
+    pub source_adapter: Option<Account<'info, StrategyAdapter>>,
+    #[account(mut)]
+    pub source_position: Option<Account<'info, Position>>,
+    /// CHECK: Must match source_adapter.program_id, checked before the CPI
+    pub source_adapter_program: Option<UncheckedAccount<'info>>,
+    pub token_program: Program<'info, Token>,
+}
+
+#[derive(Accounts)]
+pub struct InitializeCouncil<'info> {
+    pub treasury: Account<'info, Treasury>,
+    #[account(
+        init,
+        payer = authority,
+        space = Council::SIZE,
+        seeds = [b"council", treasury.key().as_ref()],
+        bump
+    )]
+    pub council: Account<'info, Council>,
+    #[account(mut)]
+    pub authority: Signer<'info>,
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+pub struct ApproveExecution<'info> {
+    pub treasury: Account<'info, Treasury>,
+    #[account(has_one = treasury)]
+    pub proposal: Account<'info, Proposal>,
+    #[account(
+        seeds = [b"council", treasury.key().as_ref()],
+        bump = council.bump
+    )]
+    pub council: Account<'info, Council>,
+    #[account(
+        init_if_needed,
+        payer = member,
+        space = ExecutionApproval::SIZE,
+        seeds = [b"execution_approval", council.key().as_ref(), proposal.key().as_ref()],
+        bump
+    )]
+    pub execution_approval: Account<'info, ExecutionApproval>,
+    #[account(mut)]
+    pub member: Signer<'info>,
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+pub struct ProposeCouncilRotation<'info> {
+    pub treasury: Account<'info, Treasury>,
+    #[account(
+        seeds = [b"council", treasury.key().as_ref()],
+        bump = council.bump
+    )]
+    pub council: Account<'info, Council>,
+    #[account(
+        init,
+        payer = proposer,
+        space = CouncilRotation::SIZE,
+        seeds = [b"council_rotation", council.key().as_ref(), proposer.key().as_ref()],
+        bump
+    )]
+    pub rotation: Account<'info, CouncilRotation>,
+    #[account(mut)]
+    pub proposer: Signer<'info>,
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+pub struct ApproveCouncilRotation<'info> {
+    pub treasury: Account<'info, Treasury>,
+    #[account(
+        seeds = [b"council", treasury.key().as_ref()],
+        bump = council.bump
+    )]
+    pub council: Account<'info, Council>,
+    #[account(
+        mut,
+        seeds = [b"council_rotation", council.key().as_ref(), rotation.proposer.as_ref()],
+        bump
+    )]
+    pub rotation: Account<'info, CouncilRotation>,
+    pub member: Signer<'info>,
+}
+
+#[derive(Accounts)]
+pub struct ExecuteCouncilRotation<'info> {
+    pub treasury: Account<'info, Treasury>,
+    #[account(
+        mut,
+        seeds = [b"council", treasury.key().as_ref()],
+        bump = council.bump
+    )]
+    pub council: Account<'info, Council>,
+    #[account(
+        mut,
+        close = proposer,
+        seeds = [b"council_rotation", council.key().as_ref(), proposer.key().as_ref()],
+        bump
+    )]
+    pub rotation: Account<'info, CouncilRotation>,
+    /// CHECK: Receives the rotation rent; bound by the rotation PDA seeds
+    #[account(mut)]
+    pub proposer: UncheckedAccount<'info>,
+    pub member: Signer<'info>,
+}
+
+#[derive(Accounts)]
+pub struct CancelCouncilRotation<'info> {
+    pub treasury: Account<'info, Treasury>,
+    #[account(
+        seeds = [b"council", treasury.key().as_ref()],
+        bump = council.bump
+    )]
+    pub council: Account<'info, Council>,
+    #[account(
+        mut,
+        close = proposer,
+        seeds = [b"council_rotation", council.key().as_ref(), proposer.key().as_ref()],
+        bump
+    )]
+    pub rotation: Account<'info, CouncilRotation>,
+    #[account(mut)]
+    pub proposer: Signer<'info>,
+}
+
+#[derive(Accounts)]
+pub struct VetoDecision<'info> {
+    pub treasury: Account<'info, Treasury>,
+    #[account(mut, has_one = treasury)]
+    pub proposal: Account<'info, Proposal>,
+    #[account(
+        seeds = [b"council", treasury.key().as_ref()],
+        bump = council.bump
+    )]
+    pub council: Account<'info, Council>,
+    pub signer: Signer<'info>,
+}
+
+#[derive(Accounts)]
+pub struct ExpireDecision<'info> {
+    pub treasury: Account<'info, Treasury>,
+    #[account(mut, has_one = treasury)]
+    pub proposal: Account<'info, Proposal>,
+}
+
+#[derive(Accounts)]
+pub struct EmergencyShutdown<'info> {
+    #[account(mut)]
+    pub treasury: Account<'info, Treasury>,
+    pub authority: Signer<'info>,
+}
+
+#[derive(Accounts)]
+pub struct ForceUnwind<'info> {
+    #[account(mut)]
+    pub treasury: Account<'info, Treasury>,
+    #[account(
+        mut,
+        seeds = [b"vault_asset", treasury.key().as_ref(), position.mint.as_ref()],
+        bump = vault_asset.bump
+    )]
+    pub vault_asset: Account<'info, VaultAsset>,
+    #[account(mut, address = vault_asset.vault @ TreasuryError::InvalidVault)]
+    pub vault: Account<'info, TokenAccount>,
+    /// CHECK: PDA used only as vault authority
+    #[account(
+        seeds = [b"vault_authority", treasury.key().as_ref()],
+        bump = treasury.vault_authority_bump
+    )]
+    pub vault_authority: UncheckedAccount<'info>,
+    #[account(address = position.adapter @ TreasuryError::PositionMismatch)]
+    pub strategy_adapter: Account<'info, StrategyAdapter>,
+    #[account(
+        mut,
+        has_one = treasury,
+        seeds = [
+            b"position",
+            treasury.key().as_ref(),
+            position.mint.as_ref(),
+            position.target_protocol.as_bytes()
+        ],
+        bump = position.bump
+    )]
+    pub position: Account<'info, Position>,
+    /// CHECK: Must match strategy_adapter.program_id, checked before the CPI
+    pub adapter_program: UncheckedAccount<'info>,
+    pub cranker: Signer<'info>,
+    pub token_program: Program<'info, Token>,
+}
+
+#[derive(Accounts)]
+pub struct EmergencyWithdraw<'info> {
+    #[account(mut)]
+    pub treasury: Account<'info, Treasury>,
+    #[account(
+        mut,
+        seeds = [b"vault_asset", treasury.key().as_ref(), vault_asset.mint.as_ref()],
+        bump = vault_asset.bump
+    )]
+    pub vault_asset: Account<'info, VaultAsset>,
+    pub user: Signer<'info>,
+    #[account(mut, token::mint = vault_asset.mint)]
+    pub user_token_account: Account<'info, TokenAccount>,
+    #[account(
+        mut,
+        address = vault_asset.vault @ TreasuryError::InvalidVault,
+        token::mint = vault_asset.mint,
+        token::authority = vault_authority
+    )]
+    pub treasury_token_account: Account<'info, TokenAccount>,
+    /// CHECK: PDA used only as vault authority
+    #[account(
+        seeds = [b"vault_authority", treasury.key().as_ref()],
+        bump = treasury.vault_authority_bump
+    )]
+    pub vault_authority: UncheckedAccount<'info>,
+    #[account(mut, address = vault_asset.share_mint)]
+    pub share_mint: Account<'info, Mint>,
+    #[account(mut, token::mint = share_mint, token::authority = user)]
+    pub user_share_account: Account<'info, TokenAccount>,
+    pub token_program: Program<'info, Token>,
+}
+
+#[derive(Accounts)]
+pub struct AdvanceEpoch<'info> {
+    #[account(mut)]
+    pub treasury: Account<'info, Treasury>,
+    #[account(
+        init,
+        payer = payer,
+        space = EpochSnapshot::SIZE,
+        seeds = [
+            b"epoch_snapshot",
+            treasury.key().as_ref(),
+            &treasury.current_epoch.to_le_bytes()
+        ],
+        bump
+    )]
+    pub epoch_snapshot: Account<'info, EpochSnapshot>,
+    #[account(mut)]
+    pub payer: Signer<'info>,
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+pub struct CancelProposal<'info> {
+    pub treasury: Account<'info, Treasury>,
+    #[account(mut, has_one = treasury)]
+    pub proposal: Account<'info, Proposal>,
+    pub signer: Signer<'info>,
+}
+
+#[derive(Accounts)]
+pub struct CloseProposal<'info> {
+    pub treasury: Account<'info, Treasury>,
+    #[account(mut, has_one = treasury, has_one = proposer, close = proposer)]
+    pub proposal: Account<'info, Proposal>,
+    /// CHECK: Receives the proposal rent; must match the recorded proposer
+    #[account(mut)]
+    pub proposer: UncheckedAccount<'info>,
+}
+
+#[derive(Accounts)]
+pub struct CloseVoteRecord<'info> {
+    /// CHECK: Status is read in the handler; may already be closed
+    #[account(address = vote.proposal)]
+    pub proposal: UncheckedAccount<'info>,
+    #[account(mut, has_one = voter, close = voter)]
+    pub vote: Account<'info, VoteRecord>,
+    /// CHECK: Receives the vote rent; must match the recorded voter
+    #[account(mut)]
+    pub voter: UncheckedAccount<'info>,
+}
+
+#[derive(Accounts)]
+pub struct CloseExecutionApproval<'info> {
+    /// CHECK: Status is read in the handler; may already be closed
+    #[account(address = execution_approval.proposal)]
+    pub proposal: UncheckedAccount<'info>,
+    #[account(mut, has_one = payer, close = payer)]
+    pub execution_approval: Account<'info, ExecutionApproval>,
+    /// CHECK: Receives the approval rent; must match the recorded payer
+    #[account(mut)]
+    pub payer: UncheckedAccount<'info>,
+}
+
+#[derive(Accounts)]
+pub struct SetOracles<'info> {
+    pub treasury: Account<'info, Treasury>,
+    #[account(
+        init_if_needed,
+        payer = authority,
+        space = OracleSet::SIZE,
+        seeds = [b"oracle_set", treasury.key().as_ref()],
+        bump
+    )]
+    pub oracle_set: Account<'info, OracleSet>,
+    #[account(mut)]
+    pub authority: Signer<'info>,
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+pub struct RecordOutcome<'info> {
+    #[account(mut)]
+    pub treasury: Account<'info, Treasury>,
+    #[account(mut, has_one = treasury)]
+    pub proposal: Account<'info, Proposal>,
+    #[account(
+        seeds = [b"oracle_set", treasury.key().as_ref()],
+        bump = oracle_set.bump
+    )]
+    pub oracle_set: Option<Account<'info, OracleSet>>,
+    pub recorder: Signer<'info>,
+    #[account(address = treasury.registry @ TreasuryError::WrongRegistry)]
+    pub registry: Account<'info, Registry>,
+    #[account(
+        mut,
+        address = proposal.decision.agent_id @ TreasuryError::UnregisteredAgent,
+        constraint = agent.registry == registry.key() @ TreasuryError::WrongRegistry
+    )]
+    pub agent: Account<'info, Agent>,
+    /// CHECK: Whitelist record validated by agent_registry during the CPI
+    pub adjuster_record: UncheckedAccount<'info>,
+    /// CHECK: PDA that signs reputation adjustments on behalf of this treasury
+    #[account(seeds = [b"reputation_authority", treasury.key().as_ref()], bump)]
+    pub reputation_authority: UncheckedAccount<'info>,
+    pub agent_registry_program: Program<'info, AgentRegistry>,
+}
+
+#[account]
+pub struct Treasury {
+    pub authority: Pubkey,
+    pub pending_authority: Option<Pubkey>,
+    pub params: TreasuryParams,
+    pub total_value_locked: u64, // Idle vault balances plus marked positions
+    pub current_epoch: u64,
+    pub last_rebalance: i64,
+    pub decision_count: u64,
+    pub vault_authority_bump: u8,
+    pub asset_count: u16,
+    pub guardian: Pubkey, // May pause but not unpause
+    pub paused: u16,      // Bitflags of PAUSE_* categories
+    pub epoch_risk_used: u64, // Sum of risk scores executed this epoch
+    pub epoch_started_at: i64,
+    pub epoch_decisions_executed: u64,
+    pub fee_recipient: Pubkey,     // Owner of the share accounts that receive fee shares
+    pub shutdown_at: i64,          // Time emergency_shutdown was called (0 = live)
+    pub registry: Pubkey,          // agent_registry instance whose agents may propose and vote
+}
+
+impl Treasury {
+    pub const SIZE: usize = 8 + // discriminator
+        32 + // authority
+        1 + 32 + // pending_authority Option
+        TreasuryParams::SIZE +
+        8 + // tvl
+        8 + // epoch
+        8 + // last_rebalance
+        8 + // decision_count
+        1 + // vault_authority_bump
+        2 + // asset_count
+        32 + // guardian
+        2 + // paused
+        8 + // epoch_risk_used
+        8 + // epoch_started_at
+        8 + // epoch_decisions_executed
+        32 + // fee_recipient
+        8 + // shutdown_at
+        32; // registry
+
+    pub fn is_paused(&self, flag: u16) -> bool {
+        self.paused & flag != 0
+    }
+
+    pub fn is_shut_down(&self) -> bool {
+        self.shutdown_at != 0
+    }
+}
+
+#[account]
+pub struct Council {
+    pub treasury: Pubkey,
+    pub members: Vec<Pubkey>,
+    pub threshold: u8,
+    pub version: u64, // Bumped on every rotation to invalidate pending approvals
+    pub bump: u8,
+}
+
+impl Council {
+    pub const SIZE: usize = 8 + // discriminator
+        32 + // treasury
+        4 + (MAX_COUNCIL_MEMBERS * 32) + // members vec
+        1 + // threshold
+        8 + // version
+        1; // bump
+
+    pub fn member_index(&self, key: &Pubkey) -> Option<usize> {
+        self.members.iter().position(|member| member == key)
+    }
+
+    pub fn is_member(&self, key: &Pubkey) -> bool {
+        self.member_index(key).is_some()
+    }
+}
+
+/// Validate a council membership set and its approval threshold
+pub fn validate_council(members: &[Pubkey], threshold: u8) -> Result<()> {
+    require!(
+        !members.is_empty() && members.len() <= MAX_COUNCIL_MEMBERS,
+        TreasuryError::InvalidCouncilConfig
+    );
+    require!(
+        threshold > 0 && threshold as usize <= members.len(),
+        TreasuryError::InvalidCouncilConfig
+    );
+    for (i, member) in members.iter().enumerate() {
+        require!(
+            !members[i + 1..].contains(member),
+            TreasuryError::InvalidCouncilConfig
+        );
+    }
+    Ok(())
+}
+
+#[account]
+pub struct EpochSnapshot {
+    pub treasury: Pubkey,
+    pub epoch: u64,
+    pub started_at: i64,
+    pub ended_at: i64,
+    pub decisions_executed: u64,
+    pub risk_used: u64,
+    pub assets: Vec<AssetSnapshot>,
+}
+
+impl EpochSnapshot {
+    pub const SIZE: usize = 8 + // discriminator
+        32 + // treasury
+        8 + // epoch
+        8 + // started_at
+        8 + // ended_at
+        8 + // decisions_executed
+        8 + // risk_used
+        4 + (MAX_VAULT_ASSETS * AssetSnapshot::SIZE); // assets vec
+}
+
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Debug)]
+pub struct AssetSnapshot {
+    pub mint: Pubkey,
+    pub balance: u64,
+    pub share_supply: u64,
+    pub share_price: u64, // SHARE_PRICE_PRECISION fixed point
+    pub realized_yield: i64, // Realized gains minus losses this epoch, in raw token units
+    pub fees: u64,           // Fees accrued this epoch, in raw token units
+}
+
+impl AssetSnapshot {
+    pub const SIZE: usize = 32 + 8 + 8 + 8 + 8 + 8;
+}
+
+#[account]
+pub struct OracleSet {
+    pub treasury: Pubkey,
+    pub oracles: Vec<Pubkey>,
+    pub bump: u8,
+}
+
+impl OracleSet {
+    pub const SIZE: usize = 8 + // discriminator
+        32 + // treasury
+        4 + (MAX_ORACLES * 32) + // oracles vec
+        1; // bump
+}
+
+#[account]
+pub struct ExecutionApproval {
+    pub council: Pubkey,
+    pub proposal: Pubkey,
+    pub council_version: u64,
+    pub approval_bitmap: u16, // Bit i set when members[i] approved
+    pub approval_count: u8,
+    pub payer: Pubkey, // Member who funded the account and receives its rent on close
+}
+
+impl ExecutionApproval {
+    pub const SIZE: usize = 8 + // discriminator
+        32 + // council
+        32 + // proposal
+        8 + // council_version
+        2 + // approval_bitmap
+        1 + // approval_count
+        32; // payer
+}
+
+#[account]
+pub struct CouncilRotation {
+    pub council: Pubkey,
+    pub proposer: Pubkey,
+    pub council_version: u64,
+    pub new_members: Vec<Pubkey>,
+    pub new_threshold: u8,
+    pub approval_bitmap: u16,
+    pub approval_count: u8,
+}
+
+impl CouncilRotation {
+    pub const SIZE: usize = 8 + // discriminator
+        32 + // council
+        32 + // proposer
+        8 + // council_version
+        4 + (MAX_COUNCIL_MEMBERS * 32) + // new_members vec
+        1 + // new_threshold
+        2 + // approval_bitmap
+        1; // approval_count
+}
+
+#[account]
+pub struct ParamsChange {
+    pub treasury: Pubkey,
+    pub proposer: Pubkey,
+    pub new_params: TreasuryParams,
+    pub new_registry: Pubkey,
+    pub eta: i64, // Earliest time update_params may apply the change
+    pub bump: u8,
+}
+
+impl ParamsChange {
+    pub const SIZE: usize = 8 + // discriminator
+        32 + // treasury
+        32 + // proposer
+        TreasuryParams::SIZE + // new_params
+        32 + // new_registry
+        8 + // eta
+        1; // bump
+}
+
+#[account]
+pub struct VaultAsset {
+    pub treasury: Pubkey,
+    pub mint: Pubkey,
+    pub vault: Pubkey,
+    pub share_mint: Pubkey,
+    pub balance: u64,  // Idle funds held in the vault
+    pub deployed: u64, // Marked value of this asset's positions
+    pub decimals: u8,
+    pub enabled: bool,
+    pub bump: u8,
+    pub high_water_mark: u64, // Highest post-fee share price, SHARE_PRICE_PRECISION fixed point
+    pub fees_accrued_at: i64,
+    pub counters_epoch: u64,       // Epoch the per-epoch counters below belong to
+    pub epoch_realized_yield: i64, // Realized gains minus losses this epoch, in raw token units
+    pub epoch_fees: u64,           // Fees accrued this epoch, in raw token units
+    pub epoch_net_outflow: i64,    // Outflows minus inflows this epoch, in raw token units
+    pub outflow_window_hour: i64,  // Hour (unix / 3600) of the newest outflow bucket
+    pub hourly_net_outflow: [i64; OUTFLOW_WINDOW_HOURS], // Ring buffer indexed by hour
+}
+
+impl VaultAsset {
+    pub const SIZE: usize = 8 + // discriminator
+        32 + // treasury
+        32 + // mint
+        32 + // vault
+        32 + // share_mint
+        8 + // balance
+        8 + // deployed
+        1 + // decimals
+        1 + // enabled
+        1 + // bump
+        8 + // high_water_mark
+        8 + // fees_accrued_at
+        8 + // counters_epoch
+        8 + // epoch_realized_yield
+        8 + // epoch_fees
+        8 + // epoch_net_outflow
+        8 + // outflow_window_hour
+        8 * OUTFLOW_WINDOW_HOURS; // hourly_net_outflow
+
+    /// Assets backing the share mint: idle balance plus marked positions
+    pub fn total_assets(&self) -> u64 {
+        self.balance.saturating_add(self.deployed)
+    }
+
+    /// Zero per-epoch counters left over from an earlier epoch
+    /// Counters reset lazily since advance_epoch does not write to every asset
+    pub fn roll_epoch(&mut self, epoch: u64) {
+        if self.counters_epoch != epoch {
+            self.counters_epoch = epoch;
+            self.epoch_realized_yield = 0;
+            self.epoch_fees = 0;
+            self.epoch_net_outflow = 0;
+        }
+    }
+
+    /// Clear hourly buckets that have fallen out of the rolling window
+    pub fn roll_outflow_window(&mut self, now: i64) {
+        let hour = now.div_euclid(SECONDS_PER_HOUR);
+        let elapsed = hour.saturating_sub(self.outflow_window_hour);
+        if elapsed <= 0 {
+            return;
+        }
+        for offset in 1..=elapsed.min(OUTFLOW_WINDOW_HOURS as i64) {
+            let slot = (self.outflow_window_hour + offset).rem_euclid(OUTFLOW_WINDOW_HOURS as i64);
+            self.hourly_net_outflow[slot as usize] = 0;
+        }
+        self.outflow_window_hour = hour;
+    }
+
+    /// Net outflow over the rolling 24h window; call roll_outflow_window first
+    pub fn daily_net_outflow(&self) -> i64 {
+        self.hourly_net_outflow
+            .iter()
+            .fold(0i64, |sum, bucket| sum.saturating_add(*bucket))
+    }
+}
+
+#[account]
+pub struct StrategyAdapter {
+    pub treasury: Pubkey,
+    pub mint: Pubkey,
+    pub target_protocol: String, // Matched against YieldDecision.target_protocol
+    pub program_id: Pubkey,
+    pub accounts: Vec<AdapterAccount>, // Appended after the fixed adapter accounts
+    pub enabled: bool,
+    pub bump: u8,
+}
+
+impl StrategyAdapter {
+    pub const SIZE: usize = 8 + // discriminator
+        32 + // treasury
+        32 + // mint
+        4 + MAX_ADAPTER_NAME_LEN + // target_protocol
+        32 + // program_id
+        4 + (MAX_ADAPTER_ACCOUNTS * AdapterAccount::SIZE) + // accounts vec
+        1 + // enabled
+        1; // bump
+}
+
+#[account]
+pub struct Position {
+    pub treasury: Pubkey,
+    pub mint: Pubkey,
+    pub target_protocol: String,
+    pub adapter: Pubkey,
+    pub principal: u64,    // Cost basis still deployed
+    pub marked_value: u64, // Latest value mark, counted in TVL
+    pub last_marked_at: i64,
+    pub opened_at: i64,    // Set when the position goes from empty to funded
+    pub opened_by: Pubkey, // Proposal that opened the position
+    pub bump: u8,
+}
+
+impl Position {
+    pub const SIZE: usize = 8 + // discriminator
+        32 + // treasury
+        32 + // mint
+        4 + MAX_ADAPTER_NAME_LEN + // target_protocol
+        32 + // adapter
+        8 + // principal
+        8 + // marked_value
+        8 + // last_marked_at
+        8 + // opened_at
+        32 + // opened_by
+        1; // bump
+}
+
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Debug, PartialEq)]
+pub struct AdapterAccount {
+    pub pubkey: Pubkey,
+    pub is_writable: bool,
+}
+
+impl AdapterAccount {
+    pub const SIZE: usize = 32 + 1;
+}
+
+/// Shares minted for a deposit of `amount` into a vault holding `total_assets`
+pub fn shares_for_deposit(amount: u64, total_assets: u64, total_shares: u64) -> Option<u64> {
+    let shares = (amount as u128)
+        .checked_mul((total_shares as u128).checked_add(VIRTUAL_SHARES as u128)?)?
+        .checked_div((total_assets as u128).checked_add(VIRTUAL_ASSETS as u128)?)?;
+    u64::try_from(shares).ok()
+}
+
+/// Reason a proposal fails its vote, if any
+/// Quorum is checked before the approval rule so rejections are attributable
+fn decision_rejection(treasury: &Treasury, proposal: &Proposal) -> Option<RejectionReason> {
+    let total_votes = proposal.votes_for.checked_add(proposal.votes_against).unwrap();
+    if total_votes < treasury.params.quorum_threshold {
+        Some(RejectionReason::QuorumNotMet)
+    } else if !treasury
+        .params
+        .approval_mode
+        .is_approved(proposal.votes_for, proposal.votes_against)
+    {
+        Some(RejectionReason::VoteThresholdNotMet)
+    } else {
+        None
+    }
+}
+
+/// Perform an approved decision's fund movement through its strategy adapters
+fn execute_strategy<'info>(
+    accounts: &mut ExecuteQueued<'info>,
+    remaining_accounts: &[AccountInfo<'info>],
+) -> Result<()> {
+    let decision = accounts.proposal.decision.clone();
+    let treasury_key = accounts.treasury.key();
+    
+    // No hedging adapter interface exists yet; hedge decisions are recorded only
+    if matches!(decision.action, YieldAction::Hedge) {
+        return Ok(());
+    }
+    
+    require!(
+        accounts.vault_asset.mint == decision.asset_mint,
+        TreasuryError::InvalidVault
+    );
+    let target = accounts
+        .strategy_adapter
+        .as_ref()
+        .ok_or(TreasuryError::StrategyAdapterRequired)?;
+    require!(
+        target.treasury == treasury_key
+            && target.mint == decision.asset_mint
+            && target.target_protocol == decision.target_protocol,
+        TreasuryError::AdapterMismatch
+    );
+    let target_program = accounts
+        .adapter_program
+        .as_ref()
+        .ok_or(TreasuryError::StrategyAdapterRequired)?;
+    require!(
+        accounts
+            .position
+            .as_ref()
+            .is_some_and(|position| position.adapter == target.key()),
+        TreasuryError::PositionMismatch
+    );
+    
+    let net_outflow = match decision.action {
+        YieldAction::Deposit => {
+            require!(target.enabled, TreasuryError::AdapterNotEnabled);
+            let (before, after) = invoke_adapter(
+                &accounts.adapter_vault(),
+                target,
+                target_program,
+                remaining_accounts,
+                ADAPTER_DEPOSIT_DISCRIMINATOR,
+                decision.amount,
+            )?;
+            let spent = before.saturating_sub(after);
+            require!(spent <= decision.amount, TreasuryError::AdapterOverdraw);
+            record_deployment(accounts, spent)?;
+            i64::try_from(spent).map_err(|_| TreasuryError::CalculationOverflow)?
+        }
+        YieldAction::Withdraw => {
+            let (before, after) = invoke_adapter(
+                &accounts.adapter_vault(),
+                target,
+                target_program,
+                remaining_accounts,
+                ADAPTER_WITHDRAW_DISCRIMINATOR,
+                decision.amount,
+            )?;
+            let received = after.saturating_sub(before);
+            record_recall(
+                &mut accounts.treasury,
+                &mut accounts.vault_asset,
+                accounts.position.as_mut().unwrap(),
+                received,
+            )?;
+            -i64::try_from(received).map_err(|_| TreasuryError::CalculationOverflow)?
+        }
+        YieldAction::Rebalance => {
+            require!(target.enabled, TreasuryError::AdapterNotEnabled);
+            let source = accounts
+                .source_adapter
+                .as_ref()
+                .ok_or(TreasuryError::StrategyAdapterRequired)?;
+            require!(
+                source.treasury == treasury_key
+                    && source.mint == decision.asset_mint
+                    && source.target_protocol == decision.source_protocol
+                    && source.key() != target.key(),
+                TreasuryError::AdapterMismatch
+            );
+            let source_program = accounts
+                .source_adapter_program
+                .as_ref()
+                .ok_or(TreasuryError::StrategyAdapterRequired)?;
+            require!(
+                accounts
+                    .source_position
+                    .as_ref()
+                    .is_some_and(|position| position.adapter == source.key()),
+                TreasuryError::PositionMismatch
+            );
+            require!(
+                remaining_accounts.len() >= source.accounts.len(),
+                TreasuryError::InvalidAdapterAccounts
+            );
+            let (source_layout, target_layout) = remaining_accounts.split_at(source.accounts.len());
+            
+            let (before, after) = invoke_adapter(
+                &accounts.adapter_vault(),
+                source,
+                source_program,
+                source_layout,
+                ADAPTER_WITHDRAW_DISCRIMINATOR,
+                decision.amount,
+            )?;
+            let received = after.saturating_sub(before);
+            
+            // Redeploy everything received, including any yield realized from the source
+            let (before, after) = invoke_adapter(
+                &accounts.adapter_vault(),
+                target,
+                target_program,
+                target_layout,
+                ADAPTER_DEPOSIT_DISCRIMINATOR,
+                received,
+            )?;
+            let spent = before.saturating_sub(after);
+            require!(spent <= received, TreasuryError::AdapterOverdraw);
+            
+            record_recall(
+                &mut accounts.treasury,
+                &mut accounts.vault_asset,
+                accounts.source_position.as_mut().unwrap(),
+                received,
+            )?;
+            record_deployment(accounts, spent)?;
+            // spent <= received, so the rebalance is a net inflow of whatever was not redeployed
+            -i64::try_from(received - spent).map_err(|_| TreasuryError::CalculationOverflow)?
+        }
+        YieldAction::Hedge => unreachable!(),
+    };
+    
+    apply_net_outflow(
+        &accounts.treasury,
+        &mut accounts.vault_asset,
+        net_outflow,
+        Clock::get()?.unix_timestamp,
+    )
+}
+
+/// True once a proposal is final or its account has been closed
+/// Proposal PDAs embed a never-reused sequence number, so a closed one cannot come back
+fn proposal_settled(proposal: &AccountInfo) -> Result<bool> {
+    if proposal.owner != &crate::ID || proposal.data_is_empty() {
+        return Ok(true);
+    }
+    let proposal = Proposal::try_deserialize(&mut &proposal.try_borrow_data()?[..])?;
+    Ok(proposal.status.is_final())
+}
+
+/// Record a change in an asset's net outflow, rejecting it if it would breach an outflow cap
+/// Positive `delta` is value leaving the vault; caps are in the asset's own raw units and
+/// bps caps are measured against its current total assets.
+/// NOTE: this deliberately departs from treasury-level accounting in bps of TVL. TVL sums
+/// raw units across mints with different decimals and prices, so a TVL-relative cap would
+/// let a low-decimal asset drain entirely while a high-decimal one dominates the total
+fn apply_net_outflow(
+    treasury: &Account<Treasury>,
+    vault_asset: &mut VaultAsset,
+    delta: i64,
+    now: i64,
+) -> Result<()> {
+    vault_asset.roll_epoch(treasury.current_epoch);
+    vault_asset.roll_outflow_window(now);
+    let epoch_net_outflow = vault_asset
+        .epoch_net_outflow
+        .checked_add(delta)
+        .ok_or(TreasuryError::CalculationOverflow)?;
+    let daily_net_outflow = vault_asset
+        .daily_net_outflow()
+        .checked_add(delta)
+        .ok_or(TreasuryError::CalculationOverflow)?;
+    
+    // Inflows only ever reduce net outflow, so they never trip a cap
+    if delta > 0 {
+        let params = &treasury.params;
+        let holdings = vault_asset.total_assets();
+        let windows = [
+            (
+                RateLimitWindow::Epoch,
+                epoch_net_outflow,
+                outflow_cap(params.epoch_outflow_cap, params.epoch_outflow_cap_bps, holdings),
+            ),
+            (
+                RateLimitWindow::Rolling24h,
+                daily_net_outflow,
+                outflow_cap(params.daily_outflow_cap, params.daily_outflow_cap_bps, holdings),
+            ),
+        ];
+        for (window, net_outflow, cap) in windows {
+            if let Some(cap) = cap {
+                if net_outflow > 0 && net_outflow as u64 > cap {
+                    // Events from a failed transaction are discarded, so log the hit instead
+                    msg!(
+                        "Outflow cap hit: mint {} window {:?} net outflow {} cap {}",
+                        vault_asset.mint,
+                        window,
+                        net_outflow,
+                        cap
+                    );
+                    return Err(TreasuryError::OutflowLimitExceeded.into());
+                }
+            }
+        }
+    }
+    
+    vault_asset.epoch_net_outflow = epoch_net_outflow;
+    let slot = now.div_euclid(SECONDS_PER_HOUR).rem_euclid(OUTFLOW_WINDOW_HOURS as i64) as usize;
+    vault_asset.hourly_net_outflow[slot] = vault_asset.hourly_net_outflow[slot]
+        .checked_add(delta)
+        .ok_or(TreasuryError::CalculationOverflow)?;
+    
+    Ok(())
+}
+
+/// Tightest of the absolute and holdings-relative caps; a zero setting disables that cap
+pub fn outflow_cap(absolute: u64, bps: u16, holdings: u64) -> Option<u64> {
+    let relative = (bps > 0).then(|| ((holdings as u128) * (bps as u128) / 10_000) as u64);
+    let absolute = (absolute > 0).then_some(absolute);
+    match (absolute, relative) {
+        (Some(a), Some(r)) => Some(a.min(r)),
+        (a, r) => a.or(r),
+    }
+}
+
+/// Vault side of an adapter CPI: the vault, the PDA that signs for it, and the token program
+struct AdapterVault<'info> {
+    treasury: Pubkey,
+    vault_authority_bump: u8,
+    vault_authority: AccountInfo<'info>,
+    vault: AccountInfo<'info>,
+    token_program: AccountInfo<'info>,
+}
+
+impl<'info> ExecuteQueued<'info> {
+    fn adapter_vault(&self) -> AdapterVault<'info> {
+        AdapterVault {
+            treasury: self.treasury.key(),
+            vault_authority_bump: self.treasury.vault_authority_bump,
+            vault_authority: self.vault_authority.to_account_info(),
+            vault: self.vault.to_account_info(),
+            token_program: self.token_program.to_account_info(),
+        }
+    }
+}
+
+impl<'info> ForceUnwind<'info> {
+    fn adapter_vault(&self) -> AdapterVault<'info> {
+        AdapterVault {
+            treasury: self.treasury.key(),
+            vault_authority_bump: self.treasury.vault_authority_bump,
+            vault_authority: self.vault_authority.to_account_info(),
+            vault: self.vault.to_account_info(),
+            token_program: self.token_program.to_account_info(),
+        }
+    }
+}
+
+/// CPI an adapter instruction signed by the vault authority
+/// Returns the vault balance before and after the call
+fn invoke_adapter<'info>(
+    accounts: &AdapterVault<'info>,
+    adapter: &StrategyAdapter,
+    adapter_program: &AccountInfo<'info>,
+    layout_accounts: &[AccountInfo<'info>],
+    discriminator: [u8; 8],
+    amount: u64,
+) -> Result<(u64, u64)> {
+    require!(
+        adapter_program.key() == adapter.program_id && adapter_program.executable,
+        TreasuryError::InvalidAdapterProgram
+    );
+    require!(
+        layout_accounts.len() == adapter.accounts.len()
+            && layout_accounts
+                .iter()
+                .zip(adapter.accounts.iter())
+                .all(|(info, expected)| info.key() == expected.pubkey),
+        TreasuryError::InvalidAdapterAccounts
+    );
+    
+    let vault_authority = accounts.vault_authority.clone();
+    let vault = accounts.vault.clone();
+    let token_program = accounts.token_program.clone();
+    
+    let mut metas = vec![
+        AccountMeta::new_readonly(vault_authority.key(), true),
+        AccountMeta::new(vault.key(), false),
+        AccountMeta::new_readonly(token_program.key(), false),
+    ];
+    metas.extend(adapter.accounts.iter().map(|account| {
+        if account.is_writable {
+            AccountMeta::new(account.pubkey, false)
+        } else {
+            AccountMeta::new_readonly(account.pubkey, false)
+        }
+    }));
+    let mut data = discriminator.to_vec();
+    data.extend_from_slice(&amount.to_le_bytes());
+    let instruction = Instruction {
+        program_id: adapter.program_id,
+        accounts: metas,
+        data,
+    };
+    
+    let mut infos = vec![vault_authority, vault.clone(), token_program];
+    infos.extend_from_slice(layout_accounts);
+    infos.push(adapter_program.clone());
+    
+    let signer_seeds: &[&[&[u8]]] = &[&[
+        b"vault_authority",
+        accounts.treasury.as_ref(),
+        &[accounts.vault_authority_bump],
+    ]];
+    
+    let before = token::accessor::amount(&vault)?;
+    invoke_signed(&instruction, &infos, signer_seeds)?;
+    let after = token::accessor::amount(&vault)?;
+    
+    Ok((before, after))
+}
+
+/// Move `amount` from idle balance into the target position at cost
+fn record_deployment(accounts: &mut ExecuteQueued<'_>, amount: u64) -> Result<()> {
+    let vault_asset = &mut accounts.vault_asset;
+    vault_asset.balance = vault_asset
+        .balance
+        .checked_sub(amount)
+        .ok_or(TreasuryError::InsufficientIdleBalance)?;
+    vault_asset.deployed = vault_asset.deployed.checked_add(amount).unwrap();
+    
+    let position = accounts.position.as_mut().unwrap();
+    if position.principal == 0 && amount > 0 {
+        position.opened_at = Clock::get()?.unix_timestamp;
+        position.opened_by = accounts.proposal.key();
+    }
+    position.principal = position.principal.checked_add(amount).unwrap();
+    position.marked_value = position.marked_value.checked_add(amount).unwrap();
+    
+    // Submission only bounds a single decision; repeated deposits into one
+    // protocol must keep the whole position under max_allocation_bps
+    require!(
+        (position.principal as u128) * 10_000
+            <= (vault_asset.total_assets() as u128) * accounts.treasury.params.max_allocation_bps as u128,
+        TreasuryError::AllocationExceedsAssetLimit
+    );
+    
+    Ok(())
+}
+
+/// Return `received` to idle balance, retiring cost basis pro-rata to the marked value
+/// and realizing the difference as yield (or loss)
+fn record_recall(
+    treasury: &mut Treasury,
+    vault_asset: &mut VaultAsset,
+    position: &mut Position,
+    received: u64,
+) -> Result<()> {
+    let value_retired = received.min(position.marked_value);
+    let basis_retired = if received >= position.marked_value {
+        position.principal
+    } else {
+        ((position.principal as u128) * (received as u128) / (position.marked_value as u128)) as u64
+    };
+    position.principal -= basis_retired;
+    position.marked_value -= value_retired;
+    
+    vault_asset.deployed = vault_asset.deployed.saturating_sub(value_retired);
+    vault_asset.balance = vault_asset.balance.checked_add(received).unwrap();
+    
+    // TVL only moves by what the mark failed to anticipate
+    treasury.total_value_locked = treasury
+        .total_value_locked
+        .checked_add(received - value_retired)
+        .unwrap();
+    vault_asset.roll_epoch(treasury.current_epoch);
+    vault_asset.epoch_realized_yield = vault_asset
+        .epoch_realized_yield
+        .checked_add(
+            i128::from(received)
+                .checked_sub(i128::from(basis_retired))
+                .and_then(|realized| i64::try_from(realized).ok())
+                .ok_or(TreasuryError::CalculationOverflow)?,
+        )
+        .ok_or(TreasuryError::CalculationOverflow)?;
+    
+    Ok(())
+}
+
+/// Mint accrued management and performance fees for an asset as shares to the fee recipient
+fn accrue_asset_fees<'info>(
+    treasury: &Account<'info, Treasury>,
+    vault_asset: &mut Account<'info, VaultAsset>,
+    share_mint: &mut Account<'info, Mint>,
+    fee_share_account: &Account<'info, TokenAccount>,
+    vault_authority: &UncheckedAccount<'info>,
+    token_program: &Program<'info, Token>,
+) -> Result<()> {
+    let now = Clock::get()?.unix_timestamp;
+    let elapsed = now.saturating_sub(vault_asset.fees_accrued_at).max(0) as u64;
+    let total_assets = vault_asset.total_assets();
+    let total_shares = share_mint.supply;
+    vault_asset.fees_accrued_at = now;
+    
+    // With no shares outstanding there is nobody to charge
+    if total_shares == 0 {
+        return Ok(());
+    }
+    
+    let fees = fee_shares(
+        total_assets,
+        total_shares,
+        elapsed,
+        &treasury.params,
+        vault_asset.high_water_mark,
+    )
+    .ok_or(TreasuryError::CalculationOverflow)?;
+    
+    if fees.shares > 0 {
+        let treasury_key = treasury.key();
+        let signer_seeds: &[&[&[u8]]] = &[&[
+            b"vault_authority",
+            treasury_key.as_ref(),
+            &[treasury.vault_authority_bump],
+        ]];
+        let cpi_accounts = MintTo {
+            mint: share_mint.to_account_info(),
+            to: fee_share_account.to_account_info(),
+            authority: vault_authority.to_account_info(),
+        };
+        let cpi_ctx = CpiContext::new_with_signer(
+            token_program.to_account_info(),
+            cpi_accounts,
+            signer_seeds,
+        );
+        token::mint_to(cpi_ctx, fees.shares)?;
+        share_mint.reload()?;
+        
+        let fee_value = fees.management_fee.saturating_add(fees.performance_fee);
+        vault_asset.roll_epoch(treasury.current_epoch);
+        vault_asset.epoch_fees = vault_asset.epoch_fees.saturating_add(fee_value);
+    }
+    
+    // The mark only ratchets up, measured after fees are charged
+    let price = share_price(total_assets, share_mint.supply)
+        .ok_or(TreasuryError::CalculationOverflow)?;
+    vault_asset.high_water_mark = vault_asset.high_water_mark.max(price);
+    
+    if fees.shares > 0 {
+        emit!(FeesAccrued {
+            treasury: treasury.key(),
+            mint: vault_asset.mint,
+            management_fee: fees.management_fee,
+            performance_fee: fees.performance_fee,
+            shares_minted: fees.shares,
+            high_water_mark: vault_asset.high_water_mark,
+        });
+    }
+    
+    Ok(())
+}
+
+/// Fees charged by one accrual
+pub struct FeeAccrual {
+    pub management_fee: u64,  // In underlying asset units
+    pub performance_fee: u64, // In underlying asset units
+    pub shares: u64,          // Shares minted to carry both fees
+}
+
+/// Fees owed on an asset and the shares that dilute holders by exactly that value
+/// Management fee is annualized on total assets; performance fee is charged on the
+/// share price gain above `high_water_mark`
+pub fn fee_shares(
+    total_assets: u64,
+    total_shares: u64,
+    elapsed: u64,
+    params: &TreasuryParams,
+    high_water_mark: u64,
+) -> Option<FeeAccrual> {
+    let management_fee = (total_assets as u128)
+        .checked_mul(params.management_fee_bps as u128)?
+        .checked_mul(elapsed as u128)?
+        / (10_000u128 * SECONDS_PER_YEAR as u128);
+    
+    // Price is assets per VIRTUAL_SHARES shares, scaled by SHARE_PRICE_PRECISION
+    let price = share_price(total_assets, total_shares)?;
+    let performance_fee = if price > high_water_mark {
+        ((price - high_water_mark) as u128)
+            .checked_mul(total_shares as u128)?
+            .checked_mul(params.performance_fee_bps as u128)?
+            / (VIRTUAL_SHARES as u128 * SHARE_PRICE_PRECISION as u128 * 10_000)
+    } else {
+        0
+    };
+    
+    // Fees can never claim more than the assets backing the shares
+    let fee_value = management_fee
+        .checked_add(performance_fee)?
+        .min(total_assets as u128);
+    let remaining = (total_assets as u128 + VIRTUAL_ASSETS as u128).checked_sub(fee_value)?;
+    let shares = fee_value
+        .checked_mul(total_shares as u128 + VIRTUAL_SHARES as u128)?
+        .checked_div(remaining)?;
+    
+    Some(FeeAccrual {
+        management_fee: u64::try_from(management_fee.min(fee_value)).ok()?,
+        performance_fee: u64::try_from(fee_value - management_fee.min(fee_value)).ok()?,
+        shares: u64::try_from(shares).ok()?,
+    })
+}
+
+/// Underlying assets per share, in SHARE_PRICE_PRECISION fixed point
+pub fn share_price(total_assets: u64, total_shares: u64) -> Option<u64> {
+    let price = ((total_assets as u128).checked_add(VIRTUAL_ASSETS as u128)?)
+        .checked_mul(VIRTUAL_SHARES as u128)?
+        .checked_mul(SHARE_PRICE_PRECISION as u128)?
+        .checked_div((total_shares as u128).checked_add(VIRTUAL_SHARES as u128)?)?;
+    u64::try_from(price).ok()
+}
+
+/// Exact pro-rata slice of `assets` for `shares` of `total_shares`, rounded down
+/// Unlike assets_for_shares there is no virtual offset, so the last holder drains the vault
+pub fn pro_rata_share(shares: u64, assets: u64, total_shares: u64) -> Option<u64> {
+    if total_shares == 0 {
+        return Some(0);
+    }
+    let amount = (shares as u128)
+        .checked_mul(assets as u128)?
+        .checked_div(total_shares as u128)?;
+    u64::try_from(amount.min(assets as u128)).ok()
+}
+
+/// Amount paid and shares burned when redeeming `shares` after shutdown
+/// The claim is priced on total assets and capped at `idle`; a capped claim burns only
+/// the shares worth what was paid, rounded up so remaining holders are never diluted
+pub fn emergency_redemption(
+    shares: u64,
+    idle: u64,
+    total_assets: u64,
+    total_shares: u64,
+) -> Option<(u64, u64)> {
+    let claim = pro_rata_share(shares, total_assets, total_shares)?;
+    let amount = claim.min(idle);
+    if amount == claim {
+        return Some((amount, shares));
+    }
+    let burned = (amount as u128)
+        .checked_mul(total_shares as u128)?
+        .div_ceil(total_assets as u128);
+    Some((amount, u64::try_from(burned).ok()?.min(shares)))
 }
 
-impl Treasury {
-    pub const SIZE: usize = 8 + // discriminator
-        32 + // authority
-        TreasuryParams::SIZE +
-        8 + // tvl
-        8 + // epoch
-        8 + // last_rebalance
-        8; // decision_count
+/// Underlying assets owed for burning `shares`, rounded down in the vault's favour
+pub fn assets_for_shares(shares: u64, total_assets: u64, total_shares: u64) -> Option<u64> {
+    let assets = (shares as u128)
+        .checked_mul((total_assets as u128).checked_add(VIRTUAL_ASSETS as u128)?)?
+        .checked_div((total_shares as u128).checked_add(VIRTUAL_SHARES as u128)?)?;
+    u64::try_from(assets.min(total_assets as u128)).ok()
 }
 
 #[derive(AnchorSerialize, AnchorDeserialize, Clone, Debug)]
@@ -244,49 +3437,196 @@ pub struct TreasuryParams {
     pub min_deposit: u64,
     pub max_allocation_bps: u16, // Basis points
     pub decision_period: i64,     // Seconds for voting
-    pub quorum_threshold: u64,    // Minimum votes required
+    pub quorum_threshold: u64,    // Minimum total vote weight required
+    pub approval_mode: ApprovalMode,
+    pub proposal_retention: i64,  // Seconds a finalized proposal is kept before it can be closed
+    pub max_risk_score: u8,       // Highest risk_score a single decision may carry (0-100)
+    pub epoch_risk_budget: u64,   // Max sum of executed risk scores per epoch
+    pub min_proposer_reputation: u16, // Minimum agent reputation to submit decisions
+    pub min_voter_reputation: u16,    // Minimum agent reputation to vote; above 500 excludes fresh registrations
+    pub allowed_proposer_types: u8,   // Bitmask of agent_type_flag values allowed to submit
+    pub epoch_duration: i64,          // Minimum seconds between advance_epoch calls
+    pub execution_delay: i64,         // Timelock between queueing and earliest execution
+    pub execution_grace_period: i64,  // Window after the eta before a queued decision expires
+    pub epoch_outflow_cap: u64,       // Max net outflow per asset per epoch, raw token units (0 = none)
+    pub epoch_outflow_cap_bps: u16,   // Max net outflow per asset per epoch as bps of its holdings (0 = none)
+    pub daily_outflow_cap: u64,       // Max net outflow per asset per rolling 24h, raw token units (0 = none)
+    pub daily_outflow_cap_bps: u16,   // Max net outflow per asset per rolling 24h as bps of its holdings (0 = none)
+    pub management_fee_bps: u16,      // Annualized on total assets, accrued per second
+    pub performance_fee_bps: u16,     // Share of share-price gains above the high-water mark
+    pub require_reasoning_reveal: bool, // Voting opens only once reasoning is revealed
 }
 
 impl TreasuryParams {
-    pub const SIZE: usize = 8 + 2 + 8 + 8;
+    pub const SIZE: usize =
+        8 + 2 + 8 + 8 + ApprovalMode::SIZE + 8 + 1 + 8 + 2 + 2 + 1 + 8 + 8 + 8 + 8 + 2 + 8 + 2 + 2 + 2 + 1;
+
+    pub fn validate(&self) -> Result<()> {
+        require!(self.approval_mode.is_valid(), TreasuryError::InvalidApprovalMode);
+        require!(self.decision_period > 0, TreasuryError::InvalidDecisionPeriod);
+        require!(
+            [
+                self.decision_period,
+                self.proposal_retention,
+                self.epoch_duration,
+                self.execution_delay,
+                self.execution_grace_period,
+            ]
+            .iter()
+            .all(|duration| (0..=MAX_PARAM_DURATION).contains(duration)),
+            TreasuryError::InvalidDuration
+        );
+        require!(self.max_allocation_bps <= 10_000, TreasuryError::InvalidBps);
+        require!(self.max_risk_score <= 100, TreasuryError::InvalidRiskScore);
+        require!(
+            self.epoch_outflow_cap_bps <= 10_000 && self.daily_outflow_cap_bps <= 10_000,
+            TreasuryError::InvalidOutflowCap
+        );
+        require!(
+            self.management_fee_bps <= 10_000 && self.performance_fee_bps <= 10_000,
+            TreasuryError::InvalidFeeBps
+        );
+        Ok(())
+    }
+}
+
+/// Reputation change for an agent whose decision produced `actual_yield_bps`
+/// Success adds 10 and failure costs 20; accuracy adds up to 20 for an exact
+/// prediction and subtracts up to 20 once the miss reaches 1,000 bps
+pub fn reputation_delta(expected_yield_bps: u16, actual_yield_bps: u16, success: bool) -> i16 {
+    let base: i16 = if success { 10 } else { -20 };
+    let error_bps = expected_yield_bps.abs_diff(actual_yield_bps);
+    let accuracy = 20 - (error_bps / 25).min(40) as i16;
+    base + accuracy
+}
+
+/// Bit representing an agent type in `TreasuryParams::allowed_proposer_types`
+pub fn agent_type_flag(agent_type: &AgentType) -> u8 {
+    1 << (agent_type.clone() as u8)
+}
+
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Debug, PartialEq)]
+pub enum ApprovalMode {
+    SimpleMajority,                       // More weight for than against
+    Supermajority { threshold_bps: u16 }, // Share of cast weight voting for
+    AbsoluteYes { min_votes_for: u64 },   // Fixed weight voting for
+}
+
+impl ApprovalMode {
+    pub const SIZE: usize = 1 + 8; // variant + largest payload
+
+    pub fn is_valid(&self) -> bool {
+        match self {
+            ApprovalMode::SimpleMajority => true,
+            ApprovalMode::Supermajority { threshold_bps } => {
+                *threshold_bps > 0 && *threshold_bps <= 10_000
+            }
+            ApprovalMode::AbsoluteYes { min_votes_for } => *min_votes_for > 0,
+        }
+    }
+
+    pub fn is_approved(&self, votes_for: u64, votes_against: u64) -> bool {
+        match self {
+            ApprovalMode::SimpleMajority => votes_for > votes_against,
+            ApprovalMode::Supermajority { threshold_bps } => {
+                let total = votes_for as u128 + votes_against as u128;
+                total > 0 && (votes_for as u128) * 10_000 >= total * (*threshold_bps as u128)
+            }
+            ApprovalMode::AbsoluteYes { min_votes_for } => votes_for >= *min_votes_for,
+        }
+    }
 }
 
 #[account]
 pub struct Proposal {
+    pub treasury: Pubkey,
+    pub proposer: Pubkey,
+    pub sequence: u64, // Per-proposer sequence number used in the PDA seeds
     pub decision: YieldDecision,
     pub reasoning_hash: [u8; 32], // Hash of AI reasoning stored off-chain
+    pub reasoning_revealed: bool,
+    pub reasoning_uri: String, // Where the revealed reasoning can be retrieved
+    pub reasoning_encoding: ContentEncoding,
     pub submitted_at: i64,
     pub voting_ends_at: i64,
     pub status: ProposalStatus,
     pub votes_for: u64,
     pub votes_against: u64,
     pub outcome: Option<Outcome>,
+    pub eta: i64,          // Earliest execution time, set when queued
+    pub finalized_at: i64, // Set when the proposal leaves Voting or Queued for good
 }
 
 impl Proposal {
     pub const SIZE: usize = 8 + // discriminator
+        32 + // treasury
+        32 + // proposer
+        8 + // sequence
         YieldDecision::SIZE +
         32 + // reasoning_hash
+        1 + // reasoning_revealed
+        4 + MAX_REASONING_URI_LEN + // reasoning_uri
+        1 + // reasoning_encoding
         8 + // submitted_at
         8 + // voting_ends_at
         1 + // status
         8 + // votes_for
         8 + // votes_against
-        1 + Outcome::SIZE; // outcome Option (1 byte discriminant + data)
+        1 + Outcome::SIZE + // outcome Option (1 byte discriminant + data)
+        8 + // eta
+        8; // finalized_at
+}
+
+#[account]
+pub struct ProposalCounter {
+    pub treasury: Pubkey,
+    pub agent: Pubkey,
+    pub next_sequence: u64,
+    pub bump: u8,
+}
+
+impl ProposalCounter {
+    pub const SIZE: usize = 8 + // discriminator
+        32 + // treasury
+        32 + // agent
+        8 + // next_sequence
+        1; // bump
+}
+
+#[account]
+pub struct VoteRecord {
+    pub proposal: Pubkey,
+    pub voter: Pubkey,
+    pub agent: Pubkey,
+    pub support: bool,
+    pub weight: u64,
+    pub voted_at: i64,
+}
+
+impl VoteRecord {
+    pub const SIZE: usize = 8 + // discriminator
+        32 + // proposal
+        32 + // voter
+        32 + // agent
+        1 + // support
+        8 + // weight
+        8; // voted_at
 }
 
 #[derive(AnchorSerialize, AnchorDeserialize, Clone, Debug)]
 pub struct YieldDecision {
     pub agent_id: Pubkey,
+    pub asset_mint: Pubkey,
     pub action: YieldAction,
     pub target_protocol: String, // "jupiter", "kamino", "marinade", etc.
+    pub source_protocol: String, // Protocol a Rebalance withdraws from; empty otherwise
     pub amount: u64,
     pub expected_yield_bps: u16, // Expected APY in basis points
     pub risk_score: u8,          // 0-100 risk assessment
 }
 
 impl YieldDecision {
-    pub const SIZE: usize = 32 + 1 + (4 + 40) + 8 + 2 + 1; // String: 4 bytes len + 40 chars max
+    pub const SIZE: usize = 32 + 32 + 1 + (4 + 40) + (4 + 40) + 8 + 2 + 1; // String: 4 bytes len + 40 chars max
 }
 
 #[derive(AnchorSerialize, AnchorDeserialize, Clone, Debug, PartialEq)]
@@ -303,6 +3643,49 @@ pub enum ProposalStatus {
     Executed,
     Rejected,
     Cancelled,
+    Queued,
+    Vetoed,
+    Expired,
+}
+
+impl ProposalStatus {
+    /// No further votes, approvals or state transitions can happen
+    pub fn is_final(&self) -> bool {
+        matches!(
+            self,
+            ProposalStatus::Executed
+                | ProposalStatus::Rejected
+                | ProposalStatus::Cancelled
+                | ProposalStatus::Vetoed
+                | ProposalStatus::Expired
+        )
+    }
+}
+
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Debug, PartialEq)]
+pub enum ContentEncoding {
+    Identity,
+    Gzip,
+    Brotli,
+    Zstd,
+}
+
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Debug, PartialEq)]
+pub enum RevealedReasoning {
+    Inline(Vec<u8>),  // Full reasoning document; hash(bytes) must equal reasoning_hash
+    Digest([u8; 32]), // Document digest; hash(uri, encoding, digest) must equal reasoning_hash
+}
+
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Debug, PartialEq)]
+pub enum RateLimitWindow {
+    Epoch,
+    Rolling24h,
+}
+
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Debug, PartialEq)]
+pub enum RejectionReason {
+    QuorumNotMet,
+    VoteThresholdNotMet,
 }
 
 #[derive(AnchorSerialize, AnchorDeserialize, Clone, Debug)]
@@ -320,6 +3703,8 @@ impl Outcome {
 pub enum TreasuryError {
     #[msg("Agent is not registered")]
     UnregisteredAgent,
+    #[msg("Agent belongs to a different registry")]
+    WrongRegistry,
     #[msg("Voting period is still active")]
     VotingPeriodActive,
     #[msg("Invalid proposal status")]
@@ -328,6 +3713,142 @@ pub enum TreasuryError {
     DecisionNotExecuted,
     #[msg("Unauthorized executor")]
     UnauthorizedExecutor,
+    #[msg("Voting period has ended")]
+    VotingPeriodEnded,
+    #[msg("Agent account is not owned by the signer")]
+    AgentOwnerMismatch,
+    #[msg("Agent is not active")]
+    AgentNotActive,
+    #[msg("Agent has no voting weight")]
+    ZeroVotingWeight,
+    #[msg("Invalid approval mode")]
+    InvalidApprovalMode,
+    #[msg("Unauthorized")]
+    Unauthorized,
+    #[msg("Asset is not enabled")]
+    AssetNotEnabled,
+    #[msg("Deposit below minimum")]
+    DepositTooSmall,
+    #[msg("Token account is not the treasury vault")]
+    InvalidVault,
+    #[msg("Invalid council configuration")]
+    InvalidCouncilConfig,
+    #[msg("Signer is not a council member")]
+    NotCouncilMember,
+    #[msg("Council member has already approved")]
+    AlreadyApproved,
+    #[msg("Not enough council approvals")]
+    InsufficientApprovals,
+    #[msg("Approval was collected under a previous council")]
+    StaleApproval,
+    #[msg("Signer is not the pending authority")]
+    NotPendingAuthority,
+    #[msg("No authority transfer is pending")]
+    NoPendingAuthority,
+    #[msg("Instruction category is paused")]
+    Paused,
+    #[msg("Unknown pause flags")]
+    InvalidPauseFlags,
+    #[msg("Signer may not change pause flags")]
+    UnauthorizedPauser,
+    #[msg("Guardian can only pause")]
+    GuardianCannotUnpause,
+    #[msg("Proposal already has votes")]
+    ProposalHasVotes,
+    #[msg("Proposal retention period has not elapsed")]
+    RetentionPeriodActive,
+    #[msg("Allocation exceeds max_allocation_bps of the asset's holdings")]
+    AllocationExceedsAssetLimit,
+    #[msg("Risk score must be between 0 and 100")]
+    InvalidRiskScore,
+    #[msg("Risk score exceeds max_risk_score")]
+    RiskScoreTooHigh,
+    #[msg("Decision would exceed the epoch risk budget")]
+    EpochRiskBudgetExceeded,
+    #[msg("Agent reputation below minimum")]
+    InsufficientReputation,
+    #[msg("Agent type may not submit decisions")]
+    AgentTypeNotAllowed,
+    #[msg("Too many oracles")]
+    TooManyOracles,
+    #[msg("Recorder is neither the authority nor a designated oracle")]
+    UnauthorizedRecorder,
+    #[msg("Outcome has already been recorded")]
+    OutcomeAlreadyRecorded,
+    #[msg("Too many vault assets")]
+    TooManyAssets,
+    #[msg("Current epoch has not ended")]
+    EpochNotEnded,
+    #[msg("Asset accounts do not match the whitelisted assets")]
+    InvalidAssetAccounts,
+    #[msg("Withdrawal exceeds the idle vault balance")]
+    InsufficientIdleBalance,
+    #[msg("Invalid strategy adapter name")]
+    InvalidAdapterName,
+    #[msg("Too many strategy adapter accounts")]
+    TooManyAdapterAccounts,
+    #[msg("Strategy adapter still holds deployed funds")]
+    AdapterHasDeployedFunds,
+    #[msg("Strategy adapter is not enabled")]
+    AdapterNotEnabled,
+    #[msg("Strategy adapter is required for this action")]
+    StrategyAdapterRequired,
+    #[msg("Strategy adapter does not match the decision")]
+    AdapterMismatch,
+    #[msg("Invalid strategy adapter program")]
+    InvalidAdapterProgram,
+    #[msg("Accounts do not match the strategy adapter layout")]
+    InvalidAdapterAccounts,
+    #[msg("Strategy adapter moved more than the approved amount")]
+    AdapterOverdraw,
+    #[msg("Timelock delay has not elapsed")]
+    TimelockActive,
+    #[msg("Timelock delay has already elapsed")]
+    TimelockElapsed,
+    #[msg("Execution window has closed")]
+    ExecutionWindowClosed,
+    #[msg("Execution window is still open")]
+    ExecutionWindowOpen,
+    #[msg("Only a council member or the guardian can veto")]
+    UnauthorizedVeto,
+    #[msg("Position does not belong to the strategy adapter")]
+    PositionMismatch,
+    #[msg("Reasoning has already been revealed")]
+    ReasoningAlreadyRevealed,
+    #[msg("Invalid reasoning URI")]
+    InvalidReasoningUri,
+    #[msg("Revealed reasoning does not match the committed hash")]
+    ReasoningHashMismatch,
+    #[msg("Reasoning must be revealed before voting")]
+    ReasoningNotRevealed,
+    #[msg("Fee bps cannot exceed 10000")]
+    InvalidFeeBps,
+    #[msg("Fee share account is not owned by the fee recipient")]
+    InvalidFeeAccount,
+    #[msg("Outflow cap bps cannot exceed 10000")]
+    InvalidOutflowCap,
+    #[msg("Net outflow limit exceeded")]
+    OutflowLimitExceeded,
+    #[msg("Decision period must be positive")]
+    InvalidDecisionPeriod,
+    #[msg("Durations must be between zero and one year")]
+    InvalidDuration,
+    #[msg("Basis point value exceeds 10,000")]
+    InvalidBps,
+    #[msg("Treasury is shut down")]
+    TreasuryShutDown,
+    #[msg("Treasury is not shut down")]
+    NotShutDown,
+    #[msg("Amount must be greater than zero")]
+    ZeroAmount,
+    #[msg("Operation would mint or burn zero shares")]
+    ZeroShares,
+    #[msg("Calculation overflow")]
+    CalculationOverflow,
+    #[msg("Asset mint has more decimals than vault shares can support")]
+    UnsupportedDecimals,
+    #[msg("Rebalances must name a distinct source protocol; other actions must not")]
+    InvalidSourceProtocol,
 }
 
 // Events
@@ -338,24 +3859,178 @@ pub struct TreasuryInitialized {
     pub timestamp: i64,
 }
 
+#[event]
+pub struct ParamsUpdateProposed {
+    pub treasury: Pubkey,
+    pub proposer: Pubkey,
+    pub new_params: TreasuryParams,
+    pub new_registry: Pubkey,
+    pub eta: i64,
+}
+
+#[event]
+pub struct ParamsUpdated {
+    pub treasury: Pubkey,
+    pub old_params: TreasuryParams,
+    pub new_params: TreasuryParams,
+    pub old_registry: Pubkey,
+    pub new_registry: Pubkey,
+}
+
+#[event]
+pub struct ParamsUpdateCancelled {
+    pub treasury: Pubkey,
+    pub cancelled_by: Pubkey,
+}
+
+#[event]
+pub struct FeeRecipientUpdated {
+    pub treasury: Pubkey,
+    pub old_recipient: Pubkey,
+    pub new_recipient: Pubkey,
+}
+
+#[event]
+pub struct FeesAccrued {
+    pub treasury: Pubkey,
+    pub mint: Pubkey,
+    pub management_fee: u64,
+    pub performance_fee: u64,
+    pub shares_minted: u64,
+    pub high_water_mark: u64,
+}
+
+#[event]
+pub struct GuardianUpdated {
+    pub treasury: Pubkey,
+    pub old_guardian: Pubkey,
+    pub new_guardian: Pubkey,
+}
+
+#[event]
+pub struct PauseUpdated {
+    pub treasury: Pubkey,
+    pub old_flags: u16,
+    pub new_flags: u16,
+    pub updated_by: Pubkey,
+}
+
+#[event]
+pub struct AssetWhitelisted {
+    pub treasury: Pubkey,
+    pub vault_asset: Pubkey,
+    pub mint: Pubkey,
+    pub vault: Pubkey,
+    pub share_mint: Pubkey,
+    pub decimals: u8,
+}
+
+#[event]
+pub struct AssetDelisted {
+    pub treasury: Pubkey,
+    pub vault_asset: Pubkey,
+    pub mint: Pubkey,
+}
+
 #[event]
 pub struct DepositEvent {
     pub treasury: Pubkey,
     pub user: Pubkey,
+    pub mint: Pubkey,
+    pub amount: u64,
+    pub shares_minted: u64,
+    pub new_asset_balance: u64,
+    pub new_tvl: u64,
+}
+
+#[event]
+pub struct WithdrawEvent {
+    pub treasury: Pubkey,
+    pub user: Pubkey,
+    pub mint: Pubkey,
+    pub shares_burned: u64,
     pub amount: u64,
+    pub new_asset_balance: u64,
     pub new_tvl: u64,
 }
 
 #[event]
 pub struct DecisionProposed {
     pub proposal: Pubkey,
+    pub proposer: Pubkey,
+    pub sequence: u64,
     pub agent: Pubkey,
+    pub asset_mint: Pubkey,
     pub action: YieldAction,
     pub target_protocol: String,
+    pub source_protocol: String,
     pub amount: u64,
     pub reasoning_hash: [u8; 32],
 }
 
+#[event]
+pub struct ReasoningRevealed {
+    pub proposal: Pubkey,
+    pub uri: String,
+    pub content_encoding: ContentEncoding,
+}
+
+#[event]
+pub struct VoteCast {
+    pub proposal: Pubkey,
+    pub voter: Pubkey,
+    pub agent: Pubkey,
+    pub support: bool,
+    pub weight: u64,
+    pub votes_for: u64,
+    pub votes_against: u64,
+}
+
+#[event]
+pub struct DecisionQueued {
+    pub proposal: Pubkey,
+    pub eta: i64,
+    pub expires_at: i64,
+}
+
+#[event]
+pub struct DecisionVetoed {
+    pub proposal: Pubkey,
+    pub vetoed_by: Pubkey,
+}
+
+#[event]
+pub struct DecisionExpired {
+    pub proposal: Pubkey,
+    pub expired_at: i64,
+}
+
+#[event]
+pub struct TreasuryShutDown {
+    pub treasury: Pubkey,
+    pub triggered_by: Pubkey,
+    pub shutdown_at: i64,
+}
+
+#[event]
+pub struct PositionUnwound {
+    pub position: Pubkey,
+    pub received: u64,
+    pub remaining_principal: u64,
+    pub remaining_value: u64,
+    pub cranked_by: Pubkey,
+}
+
+#[event]
+pub struct EmergencyWithdrawal {
+    pub treasury: Pubkey,
+    pub user: Pubkey,
+    pub mint: Pubkey,
+    pub shares_burned: u64,
+    pub amount: u64,
+    pub remaining_deployed: u64,
+}
+
 #[event]
 pub struct DecisionExecuted {
     pub proposal: Pubkey,
@@ -368,6 +4043,118 @@ pub struct DecisionRejected {
     pub proposal: Pubkey,
     pub votes_for: u64,
     pub votes_against: u64,
+    pub quorum_threshold: u64,
+    pub reason: RejectionReason,
+}
+
+#[event]
+pub struct CouncilInitialized {
+    pub council: Pubkey,
+    pub treasury: Pubkey,
+    pub members: Vec<Pubkey>,
+    pub threshold: u8,
+}
+
+#[event]
+pub struct ExecutionApproved {
+    pub proposal: Pubkey,
+    pub member: Pubkey,
+    pub approval_count: u8,
+    pub threshold: u8,
+}
+
+#[event]
+pub struct CouncilRotationProposed {
+    pub council: Pubkey,
+    pub rotation: Pubkey,
+    pub proposer: Pubkey,
+    pub new_members: Vec<Pubkey>,
+    pub new_threshold: u8,
+}
+
+#[event]
+pub struct CouncilRotationApproved {
+    pub council: Pubkey,
+    pub rotation: Pubkey,
+    pub member: Pubkey,
+    pub approval_count: u8,
+}
+
+#[event]
+pub struct CouncilRotated {
+    pub council: Pubkey,
+    pub old_members: Vec<Pubkey>,
+    pub new_members: Vec<Pubkey>,
+    pub new_threshold: u8,
+    pub version: u64,
+}
+
+#[event]
+pub struct CouncilRotationCancelled {
+    pub council: Pubkey,
+    pub rotation: Pubkey,
+    pub proposer: Pubkey,
+}
+
+#[event]
+pub struct StrategyAdapterRegistered {
+    pub treasury: Pubkey,
+    pub adapter: Pubkey,
+    pub mint: Pubkey,
+    pub target_protocol: String,
+    pub program_id: Pubkey,
+}
+
+#[event]
+pub struct PositionMarked {
+    pub position: Pubkey,
+    pub previous_value: u64,
+    pub marked_value: u64,
+    pub new_tvl: u64,
+    pub recorder: Pubkey,
+}
+
+#[event]
+pub struct StrategyAdapterDisabled {
+    pub treasury: Pubkey,
+    pub adapter: Pubkey,
+}
+
+#[event]
+pub struct EpochAdvanced {
+    pub treasury: Pubkey,
+    pub snapshot: Pubkey,
+    pub closed_epoch: u64,
+    pub new_epoch: u64,
+    pub decisions_executed: u64,
+}
+
+#[event]
+pub struct ProposalCancelled {
+    pub proposal: Pubkey,
+    pub cancelled_by: Pubkey,
+    pub by_authority: bool,
+}
+
+#[event]
+pub struct ProposalClosed {
+    pub proposal: Pubkey,
+    pub proposer: Pubkey,
+    pub rent_returned: u64,
+}
+
+#[event]
+pub struct VoteRecordClosed {
+    pub vote: Pubkey,
+    pub proposal: Pubkey,
+    pub voter: Pubkey,
+}
+
+#[event]
+pub struct ExecutionApprovalClosed {
+    pub approval: Pubkey,
+    pub proposal: Pubkey,
+    pub payer: Pubkey,
 }
 
 #[event]
@@ -377,4 +4164,227 @@ pub struct OutcomeRecorded {
     pub predicted_yield_bps: u16,
     pub actual_yield_bps: u16,
     pub success: bool,
+    pub recorder: Pubkey,
+    pub reputation_delta: i16,
+}
+
+#[event]
+pub struct OraclesUpdated {
+    pub treasury: Pubkey,
+    pub oracles: Vec<Pubkey>,
+}
+
+#[event]
+pub struct AuthorityTransferProposed {
+    pub treasury: Pubkey,
+    pub current_authority: Pubkey,
+    pub pending_authority: Pubkey,
+}
+
+#[event]
+pub struct AuthorityTransferAccepted {
+    pub treasury: Pubkey,
+    pub old_authority: Pubkey,
+    pub new_authority: Pubkey,
+}
+
+#[event]
+pub struct AuthorityTransferCancelled {
+    pub treasury: Pubkey,
+    pub authority: Pubkey,
+    pub cancelled_authority: Pubkey,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn params(management_fee_bps: u16, performance_fee_bps: u16) -> TreasuryParams {
+        TreasuryParams {
+            min_deposit: 1,
+            max_allocation_bps: 5000,
+            decision_period: 3600,
+            quorum_threshold: 100,
+            approval_mode: ApprovalMode::SimpleMajority,
+            proposal_retention: 0,
+            max_risk_score: 100,
+            epoch_risk_budget: 1_000,
+            min_proposer_reputation: 0,
+            min_voter_reputation: 0,
+            allowed_proposer_types: 0b11111,
+            epoch_duration: 604_800,
+            execution_delay: 0,
+            execution_grace_period: 0,
+            epoch_outflow_cap: 0,
+            epoch_outflow_cap_bps: 0,
+            daily_outflow_cap: 0,
+            daily_outflow_cap_bps: 0,
+            management_fee_bps,
+            performance_fee_bps,
+            require_reasoning_reveal: false,
+        }
+    }
+
+    fn vault_asset(outflow_window_hour: i64, bucket: i64) -> VaultAsset {
+        VaultAsset {
+            treasury: Pubkey::new_unique(),
+            mint: Pubkey::new_unique(),
+            vault: Pubkey::new_unique(),
+            share_mint: Pubkey::new_unique(),
+            balance: 0,
+            deployed: 0,
+            decimals: 6,
+            enabled: true,
+            bump: 0,
+            high_water_mark: SHARE_PRICE_PRECISION,
+            fees_accrued_at: 0,
+            counters_epoch: 0,
+            epoch_realized_yield: 0,
+            epoch_fees: 0,
+            epoch_net_outflow: 0,
+            outflow_window_hour,
+            hourly_net_outflow: [bucket; OUTFLOW_WINDOW_HOURS],
+        }
+    }
+
+    #[test]
+    fn first_deposit_round_trips_at_initial_price() {
+        let shares = shares_for_deposit(1_000_000, 0, 0).unwrap();
+
+        assert_eq!(shares, 1_000_000 * VIRTUAL_SHARES);
+        assert_eq!(share_price(1_000_000, shares), Some(SHARE_PRICE_PRECISION));
+        assert_eq!(assets_for_shares(shares, 1_000_000, shares), Some(1_000_000));
+    }
+
+    #[test]
+    fn max_decimal_asset_deposits_up_to_share_capacity() {
+        // u64::MAX / 10^(9 + 3) is ~18.4M tokens of a 9-decimal asset
+        let capacity = u64::MAX / VIRTUAL_SHARES;
+        assert!(capacity / 10u64.pow(MAX_ASSET_DECIMALS as u32) >= 18_000_000);
+
+        let shares = shares_for_deposit(capacity, 0, 0).unwrap();
+        assert_eq!(share_price(capacity, shares), Some(SHARE_PRICE_PRECISION));
+        assert_eq!(assets_for_shares(shares, capacity, shares), Some(capacity));
+        assert_eq!(shares_for_deposit(capacity + 1, 0, 0), None);
+    }
+
+    #[test]
+    fn zero_supply_prices_at_precision_and_owes_nothing() {
+        assert_eq!(share_price(0, 0), Some(SHARE_PRICE_PRECISION));
+        assert_eq!(assets_for_shares(0, 1_000, 0), Some(0));
+        assert_eq!(pro_rata_share(10, 1_000, 0), Some(0));
+
+        let fees = fee_shares(0, 0, SECONDS_PER_YEAR, &params(200, 2000), SHARE_PRICE_PRECISION).unwrap();
+        assert_eq!(fees.management_fee, 0);
+        assert_eq!(fees.performance_fee, 0);
+        assert_eq!(fees.shares, 0);
+    }
+
+    #[test]
+    fn no_performance_fee_below_high_water_mark() {
+        let supply = 1_000_000 * VIRTUAL_SHARES;
+        // A 10% loss leaves the price under the initial mark
+        let fees = fee_shares(900_000, supply, 0, &params(0, 2000), SHARE_PRICE_PRECISION).unwrap();
+
+        assert!(share_price(900_000, supply).unwrap() < SHARE_PRICE_PRECISION);
+        assert_eq!(fees.performance_fee, 0);
+        assert_eq!(fees.shares, 0);
+    }
+
+    #[test]
+    fn fee_shares_carry_exactly_the_fee_value() {
+        let supply = 1_000_000 * VIRTUAL_SHARES;
+        // 10% gain above the mark at 20% performance fee, plus a year of 2% management fee
+        let fees =
+            fee_shares(1_100_000, supply, SECONDS_PER_YEAR, &params(200, 2000), SHARE_PRICE_PRECISION)
+                .unwrap();
+
+        assert_eq!(fees.management_fee, 22_000);
+        // The virtual offset prices the gain a hair under 10%
+        assert!(fees.performance_fee.abs_diff(20_000) <= 1);
+        let fee_value = fees.management_fee + fees.performance_fee;
+        let value = assets_for_shares(fees.shares, 1_100_000, supply + fees.shares).unwrap();
+        assert!(value.abs_diff(fee_value) <= 1);
+    }
+
+    #[test]
+    fn roll_outflow_window_clears_only_expired_hours() {
+        let mut asset = vault_asset(100, 5);
+
+        asset.roll_outflow_window(102 * SECONDS_PER_HOUR);
+
+        assert_eq!(asset.outflow_window_hour, 102);
+        assert_eq!(asset.daily_net_outflow(), 22 * 5);
+        assert_eq!(asset.hourly_net_outflow[(101 % OUTFLOW_WINDOW_HOURS as i64) as usize], 0);
+        assert_eq!(asset.hourly_net_outflow[(102 % OUTFLOW_WINDOW_HOURS as i64) as usize], 0);
+    }
+
+    #[test]
+    fn roll_outflow_window_clears_everything_after_a_full_window() {
+        let mut asset = vault_asset(100, 5);
+
+        asset.roll_outflow_window((100 + OUTFLOW_WINDOW_HOURS as i64 + 6) * SECONDS_PER_HOUR);
+
+        assert_eq!(asset.outflow_window_hour, 130);
+        assert_eq!(asset.daily_net_outflow(), 0);
+
+        // Time running backwards leaves the window untouched
+        let mut asset = vault_asset(100, 5);
+        asset.roll_outflow_window(99 * SECONDS_PER_HOUR);
+        assert_eq!(asset.outflow_window_hour, 100);
+        assert_eq!(asset.daily_net_outflow(), OUTFLOW_WINDOW_HOURS as i64 * 5);
+    }
+
+    #[test]
+    fn negative_net_outflow_stays_under_every_cap() {
+        // Net inflows over the window sum to a negative outflow
+        let asset = vault_asset(100, -250);
+        let daily = asset.daily_net_outflow();
+
+        assert_eq!(daily, -250 * OUTFLOW_WINDOW_HOURS as i64);
+        let cap = outflow_cap(1, 0, 0).unwrap();
+        assert!(!(daily > 0 && daily as u64 > cap));
+    }
+
+    #[test]
+    fn emergency_redemption_burns_only_what_idle_pays() {
+        // Half the assets are idle: a holder of 10% is paid its full claim of 100
+        assert_eq!(emergency_redemption(100, 500, 1_000, 1_000), Some((100, 100)));
+        // With 50 idle, a holder of 20% takes 50 of its 200 and keeps the other 150 shares
+        assert_eq!(emergency_redemption(200, 50, 1_000, 1_000), Some((50, 50)));
+        assert_eq!(emergency_redemption(200, 0, 1_000, 1_000), Some((0, 0)));
+
+        // Rounding up the burn keeps the remaining shares' claim intact
+        let (amount, burned) = emergency_redemption(300, 100, 1_000, 3_000).unwrap();
+        assert_eq!((amount, burned), (100, 300));
+        let (amount, burned) = emergency_redemption(1_000, 101, 1_000, 3_000).unwrap();
+        assert_eq!((amount, burned), (101, 303));
+    }
+
+    #[test]
+    fn outflow_cap_takes_the_tighter_limit() {
+        assert_eq!(outflow_cap(0, 0, 10_000), None);
+        assert_eq!(outflow_cap(500, 0, 10_000), Some(500));
+        assert_eq!(outflow_cap(0, 1000, 10_000), Some(1_000));
+        assert_eq!(outflow_cap(500, 1000, 10_000), Some(500));
+        assert_eq!(outflow_cap(5_000, 1000, 10_000), Some(1_000));
+        assert_eq!(outflow_cap(0, 1000, 0), Some(0));
+    }
+
+    #[test]
+    fn validate_bounds_durations_to_a_year() {
+        let mut p = params(0, 0);
+        p.execution_delay = MAX_PARAM_DURATION;
+        p.execution_grace_period = MAX_PARAM_DURATION;
+        assert!(p.validate().is_ok());
+
+        p.execution_delay = i64::MAX;
+        assert!(p.validate().is_err());
+        p.execution_delay = 0;
+        p.decision_period = MAX_PARAM_DURATION + 1;
+        assert!(p.validate().is_err());
+        p.decision_period = 3600;
+        p.epoch_duration = -1;
+        assert!(p.validate().is_err());
+    }
 }
@@ -34,72 +34,6 @@ describe("Aethernaut Integration Tests", () => {
     console.log("Market Program ID:", marketProgram.programId.toString());
   });
 
-  describe("Treasury Program", () => {
-    it("Initialize Treasury", async () => {
-      // Generate treasury PDA
-      [treasury] = PublicKey.findProgramAddressSync(
-        [Buffer.from("treasury"), wallet.publicKey.toBuffer()],
-        treasuryProgram.programId
-      );
-
-      const params = {
-        minDeposit: new anchor.BN(1_000_000), // 1 USDC
-        maxAllocationBps: 5000, // 50%
-        decisionPeriod: new anchor.BN(3600), // 1 hour
-        quorumThreshold: new anchor.BN(100),
-      };
-
-      await treasuryProgram.methods
-        .initialize(params)
-        .accounts({
-          treasury,
-          authority: wallet.publicKey,
-          systemProgram: SystemProgram.programId,
-        })
-        .rpc();
-
-      const treasuryAccount = await treasuryProgram.account.treasury.fetch(treasury);
-      assert.equal(treasuryAccount.authority.toString(), wallet.publicKey.toString());
-      assert.equal(treasuryAccount.totalValueLocked.toNumber(), 0);
-      console.log("✅ Treasury initialized");
-    });
-
-    it("Submit Yield Decision", async () => {
-      // Generate proposal PDA
-      const [proposal] = PublicKey.findProgramAddressSync(
-        [Buffer.from("proposal"), treasury.toBuffer(), Buffer.from([0, 0, 0, 0, 0, 0, 0, 0])],
-        treasuryProgram.programId
-      );
-
-      const decision = {
-        agentId: wallet.publicKey,
-        action: { deposit: {} },
-        targetProtocol: "jupiter",
-        amount: new anchor.BN(100_000_000), // 100 USDC
-        expectedYieldBps: 1250, // 12.5%
-        riskScore: 30,
-      };
-
-      const reasoningHash = Buffer.alloc(32, 1);
-
-      // This will fail without agent registry, but tests the interface
-      try {
-        await treasuryProgram.methods
-          .submitDecision(decision, reasoningHash)
-          .accounts({
-            treasury,
-            proposal,
-            agent: wallet.publicKey,
-            agentRegistry: wallet.publicKey, // Mock for test
-            systemProgram: SystemProgram.programId,
-          })
-          .rpc();
-      } catch (e) {
-        console.log("Expected error (agent not registered):", e.message);
-      }
-    });
-  });
-
   describe("Agent Registry Program", () => {
     it("Initialize Registry", async () => {
       [registry] = PublicKey.findProgramAddressSync(
@@ -183,6 +117,91 @@ describe("Aethernaut Integration Tests", () => {
     });
   });
 
+  describe("Treasury Program", () => {
+    it("Initialize Treasury", async () => {
+      // Generate treasury PDA
+      [treasury] = PublicKey.findProgramAddressSync(
+        [Buffer.from("treasury"), wallet.publicKey.toBuffer()],
+        treasuryProgram.programId
+      );
+
+      const params = {
+        minDeposit: new anchor.BN(1_000_000), // 1 USDC
+        maxAllocationBps: 5000, // 50%
+        decisionPeriod: new anchor.BN(3600), // 1 hour
+        quorumThreshold: new anchor.BN(100),
+        approvalMode: { simpleMajority: {} },
+        proposalRetention: new anchor.BN(2_592_000), // 30 days
+        maxRiskScore: 70,
+        epochRiskBudget: new anchor.BN(300),
+        minProposerReputation: 600,
+        minVoterReputation: 600,
+        allowedProposerTypes: 0b10100, // Arbiter | Oracle
+        epochDuration: new anchor.BN(604_800), // 7 days
+        executionDelay: new anchor.BN(86_400), // 1 day
+        executionGracePeriod: new anchor.BN(259_200), // 3 days
+        epochOutflowCap: new anchor.BN(0), // No absolute cap
+        epochOutflowCapBps: 2000, // 20% of each asset's holdings
+        dailyOutflowCap: new anchor.BN(0),
+        dailyOutflowCapBps: 1000, // 10% of each asset's holdings
+        managementFeeBps: 200, // 2% per year
+        performanceFeeBps: 2000, // 20% above high-water mark
+        requireReasoningReveal: false,
+      };
+
+      await treasuryProgram.methods
+        .initialize(params)
+        .accounts({
+          treasury,
+          registry, // Initialized above; only its agents may propose and vote
+          authority: wallet.publicKey,
+          systemProgram: SystemProgram.programId,
+        })
+        .rpc();
+
+      const treasuryAccount = await treasuryProgram.account.treasury.fetch(treasury);
+      assert.equal(treasuryAccount.authority.toString(), wallet.publicKey.toString());
+      assert.equal(treasuryAccount.totalValueLocked.toNumber(), 0);
+      console.log("✅ Treasury initialized");
+    });
+
+    it("Submit Yield Decision", async () => {
+      // Generate proposal PDA
+      const [proposal] = PublicKey.findProgramAddressSync(
+        [Buffer.from("proposal"), treasury.toBuffer(), wallet.publicKey.toBuffer(), Buffer.alloc(8)],
+        treasuryProgram.programId
+      );
+
+      const decision = {
+        agentId: wallet.publicKey,
+        action: { deposit: {} },
+        targetProtocol: "jupiter",
+        sourceProtocol: "",
+        amount: new anchor.BN(100_000_000), // 100 USDC
+        expectedYieldBps: 1250, // 12.5%
+        riskScore: 30,
+      };
+
+      const reasoningHash = Buffer.alloc(32, 1);
+
+      // This will fail without agent registry, but tests the interface
+      try {
+        await treasuryProgram.methods
+          .submitDecision(decision, reasoningHash)
+          .accounts({
+            treasury,
+            proposal,
+            agent: wallet.publicKey,
+            agentRegistry: wallet.publicKey, // Mock for test
+            systemProgram: SystemProgram.programId,
+          })
+          .rpc();
+      } catch (e) {
+        console.log("Expected error (agent not registered):", e.message);
+      }
+    });
+  });
+
   describe("Prediction Market Program", () => {
     it("Initialize Market", async () => {
       [market] = PublicKey.findProgramAddressSync(
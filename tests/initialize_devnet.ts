@@ -32,64 +32,82 @@ async function initializePrograms() {
   const registryProgram = new anchor.Program(registryIdl, provider);
   const marketProgram = new anchor.Program(marketIdl, provider);
 
-  // Initialize Treasury
-  console.log("🏛️ Initializing Treasury...");
-  const [treasuryPda] = PublicKey.findProgramAddressSync(
-    [Buffer.from("treasury"), deployerKeypair.publicKey.toBuffer()],
-    TREASURY_PROGRAM_ID
+  // Initialize Agent Registry
+  console.log("🤝 Initializing Agent Registry...");
+  const [registryPda] = PublicKey.findProgramAddressSync(
+    [Buffer.from("registry"), deployerKeypair.publicKey.toBuffer()],
+    REGISTRY_PROGRAM_ID
   );
 
   try {
-    await treasuryProgram.methods
+    await registryProgram.methods
       .initialize({
-        minDeposit: new anchor.BN(1_000_000),
-        maxAllocationBps: 5000,
-        decisionPeriod: new anchor.BN(3600),
-        quorumThreshold: new anchor.BN(100),
+        minStake: new anchor.BN(1_000_000),
+        reputationDecayRate: 100,
+        taskTimeoutSlashBps: 500,
       })
       .accounts({
-        treasury: treasuryPda,
+        registry: registryPda,
         authority: deployerKeypair.publicKey,
         systemProgram: SystemProgram.programId,
       })
       .rpc();
 
-    console.log("  ✅ Treasury initialized at:", treasuryPda.toString());
-    console.log("  📊 Treasury PDA created successfully");
+    console.log("  ✅ Agent Registry initialized at:", registryPda.toString());
+    console.log("  📊 Registry PDA created successfully");
   } catch (e: any) {
     if (e.message.includes("already in use")) {
-      console.log("  ℹ️ Treasury already initialized at:", treasuryPda.toString());
+      console.log("  ℹ️ Agent Registry already initialized at:", registryPda.toString());
     } else {
       console.log("  ❌ Error:", e.message);
     }
   }
 
-  // Initialize Agent Registry
-  console.log("\n🤝 Initializing Agent Registry...");
-  const [registryPda] = PublicKey.findProgramAddressSync(
-    [Buffer.from("registry"), deployerKeypair.publicKey.toBuffer()],
-    REGISTRY_PROGRAM_ID
+  // Initialize Treasury
+  console.log("\n🏛️ Initializing Treasury...");
+  const [treasuryPda] = PublicKey.findProgramAddressSync(
+    [Buffer.from("treasury"), deployerKeypair.publicKey.toBuffer()],
+    TREASURY_PROGRAM_ID
   );
 
   try {
-    await registryProgram.methods
+    await treasuryProgram.methods
       .initialize({
-        minStake: new anchor.BN(1_000_000),
-        reputationDecayRate: 100,
-        taskTimeoutSlashBps: 500,
+        minDeposit: new anchor.BN(1_000_000),
+        maxAllocationBps: 5000,
+        decisionPeriod: new anchor.BN(3600),
+        quorumThreshold: new anchor.BN(100),
+        approvalMode: { simpleMajority: {} },
+        proposalRetention: new anchor.BN(2_592_000), // 30 days
+        maxRiskScore: 70,
+        epochRiskBudget: new anchor.BN(300),
+        minProposerReputation: 600,
+        minVoterReputation: 600,
+        allowedProposerTypes: 0b10100, // Arbiter | Oracle
+        epochDuration: new anchor.BN(604_800), // 7 days
+        executionDelay: new anchor.BN(86_400), // 1 day
+        executionGracePeriod: new anchor.BN(259_200), // 3 days
+        epochOutflowCap: new anchor.BN(0), // No absolute cap
+        epochOutflowCapBps: 2000, // 20% of each asset's holdings
+        dailyOutflowCap: new anchor.BN(0),
+        dailyOutflowCapBps: 1000, // 10% of each asset's holdings
+        managementFeeBps: 200, // 2% per year
+        performanceFeeBps: 2000, // 20% above high-water mark
+        requireReasoningReveal: false,
       })
       .accounts({
+        treasury: treasuryPda,
         registry: registryPda,
         authority: deployerKeypair.publicKey,
         systemProgram: SystemProgram.programId,
       })
       .rpc();
 
-    console.log("  ✅ Agent Registry initialized at:", registryPda.toString());
-    console.log("  📊 Registry PDA created successfully");
+    console.log("  ✅ Treasury initialized at:", treasuryPda.toString());
+    console.log("  📊 Treasury PDA created successfully");
   } catch (e: any) {
     if (e.message.includes("already in use")) {
-      console.log("  ℹ️ Agent Registry already initialized at:", registryPda.toString());
+      console.log("  ℹ️ Treasury already initialized at:", treasuryPda.toString());
     } else {
       console.log("  ❌ Error:", e.message);
     }
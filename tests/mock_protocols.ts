@@ -0,0 +1,220 @@
+import * as anchor from "@coral-xyz/anchor";
+import { Program } from "@coral-xyz/anchor";
+import { MockLending } from "../target/types/mock_lending";
+import { MockSwap } from "../target/types/mock_swap";
+import { MockStaking } from "../target/types/mock_staking";
+import { PublicKey } from "@solana/web3.js";
+import {
+  createMint,
+  createAccount,
+  getAccount,
+  mintTo,
+} from "@solana/spl-token";
+import { assert } from "chai";
+
+describe("Mock Protocols", () => {
+  // Runs against the local validator only; no mainnet programs are cloned
+  anchor.setProvider(anchor.AnchorProvider.env());
+
+  const provider = anchor.getProvider() as anchor.AnchorProvider;
+  const wallet = provider.wallet as anchor.Wallet;
+  const payer = wallet.payer;
+
+  const lendingProgram = anchor.workspace.MockLending as Program<MockLending>;
+  const swapProgram = anchor.workspace.MockSwap as Program<MockSwap>;
+  const stakingProgram = anchor.workspace.MockStaking as Program<MockStaking>;
+
+  let mint: PublicKey;
+  let outputMint: PublicKey;
+  let userToken: PublicKey;
+  let userOutput: PublicKey;
+
+  const balance = async (account: PublicKey) =>
+    Number((await getAccount(provider.connection, account)).amount);
+
+  before(async () => {
+    mint = await createMint(provider.connection, payer, wallet.publicKey, null, 6);
+    outputMint = await createMint(provider.connection, payer, wallet.publicKey, null, 6);
+    userToken = await createAccount(provider.connection, payer, mint, wallet.publicKey);
+    userOutput = await createAccount(provider.connection, payer, outputMint, wallet.publicKey);
+    await mintTo(provider.connection, payer, mint, userToken, payer, 10_000_000);
+    await mintTo(provider.connection, payer, outputMint, userOutput, payer, 10_000_000);
+  });
+
+  describe("Mock Lending", () => {
+    let market: PublicKey;
+    let reserve: PublicKey;
+    let receipt: PublicKey;
+
+    it("Initializes a market and opens a receipt", async () => {
+      [market] = PublicKey.findProgramAddressSync(
+        [Buffer.from("market"), mint.toBuffer()],
+        lendingProgram.programId
+      );
+      [reserve] = PublicKey.findProgramAddressSync(
+        [Buffer.from("reserve"), market.toBuffer()],
+        lendingProgram.programId
+      );
+      [receipt] = PublicKey.findProgramAddressSync(
+        [Buffer.from("receipt"), market.toBuffer(), wallet.publicKey.toBuffer()],
+        lendingProgram.programId
+      );
+
+      await lendingProgram.methods
+        .initializeMarket(500) // 5% APR
+        .accounts({ mint, authority: wallet.publicKey })
+        .rpc();
+      await lendingProgram.methods
+        .openReceipt(wallet.publicKey)
+        .accounts({ market, payer: wallet.publicKey })
+        .rpc();
+
+      const marketAccount = await lendingProgram.account.lendingMarket.fetch(market);
+      assert.equal(marketAccount.rateBps, 500);
+    });
+
+    it("Deposits and withdraws through the adapter interface", async () => {
+      await lendingProgram.methods
+        .fundReserve(new anchor.BN(100_000))
+        .accounts({ market, reserve, source: userToken, funder: wallet.publicKey })
+        .rpc();
+      await lendingProgram.methods
+        .deposit(new anchor.BN(1_000_000))
+        .accounts({ owner: wallet.publicKey, source: userToken, market, reserve, receipt })
+        .rpc();
+
+      const deposited = await lendingProgram.account.receipt.fetch(receipt);
+      assert.isAtLeast(deposited.balance.toNumber(), 1_000_000);
+
+      await lendingProgram.methods
+        .withdraw(new anchor.BN(1_000_000))
+        .accounts({ owner: wallet.publicKey, destination: userToken, market, reserve, receipt })
+        .rpc();
+
+      assert.equal(await balance(reserve), 100_000);
+    });
+  });
+
+  describe("Mock Swap", () => {
+    let pool: PublicKey;
+    let inputReserve: PublicKey;
+    let outputReserve: PublicKey;
+    let holding: PublicKey;
+
+    it("Initializes a pool at a fixed rate", async () => {
+      [pool] = PublicKey.findProgramAddressSync(
+        [Buffer.from("pool"), mint.toBuffer(), outputMint.toBuffer()],
+        swapProgram.programId
+      );
+      [inputReserve] = PublicKey.findProgramAddressSync(
+        [Buffer.from("input_reserve"), pool.toBuffer()],
+        swapProgram.programId
+      );
+      [outputReserve] = PublicKey.findProgramAddressSync(
+        [Buffer.from("output_reserve"), pool.toBuffer()],
+        swapProgram.programId
+      );
+      [holding] = PublicKey.findProgramAddressSync(
+        [Buffer.from("holding"), pool.toBuffer(), wallet.publicKey.toBuffer()],
+        swapProgram.programId
+      );
+
+      await swapProgram.methods
+        .initializePool(new anchor.BN(2_000_000)) // 2 output per input
+        .accounts({ inputMint: mint, outputMint, authority: wallet.publicKey })
+        .rpc();
+      await swapProgram.methods
+        .openHolding(wallet.publicKey)
+        .accounts({ pool, payer: wallet.publicKey })
+        .rpc();
+      await swapProgram.methods
+        .fundReserve(new anchor.BN(5_000_000))
+        .accounts({ pool, reserve: outputReserve, source: userOutput, funder: wallet.publicKey })
+        .rpc();
+    });
+
+    it("Swaps at the configured rate", async () => {
+      const before = await balance(userOutput);
+      await swapProgram.methods
+        .swap(new anchor.BN(100_000), new anchor.BN(200_000), true)
+        .accounts({
+          pool,
+          inputReserve,
+          outputReserve,
+          userSource: userToken,
+          userDestination: userOutput,
+          user: wallet.publicKey,
+        })
+        .rpc();
+
+      assert.equal((await balance(userOutput)) - before, 200_000);
+    });
+
+    it("Realizes a deterministic gain when the rate moves", async () => {
+      await swapProgram.methods
+        .deposit(new anchor.BN(1_000_000))
+        .accounts({ owner: wallet.publicKey, source: userToken, pool, inputReserve, outputReserve, holding })
+        .rpc();
+      assert.equal((await swapProgram.account.holding.fetch(holding)).outputBalance.toNumber(), 2_000_000);
+
+      // Output doubles in value: the same holding now redeems for twice the input
+      await swapProgram.methods
+        .setRate(new anchor.BN(1_000_000))
+        .accounts({ pool, authority: wallet.publicKey })
+        .rpc();
+      await swapProgram.methods
+        .withdraw(new anchor.BN(1_100_000))
+        .accounts({ owner: wallet.publicKey, destination: userToken, pool, inputReserve, outputReserve, holding })
+        .rpc();
+
+      assert.equal((await swapProgram.account.holding.fetch(holding)).outputBalance.toNumber(), 900_000);
+    });
+  });
+
+  describe("Mock Staking", () => {
+    let pool: PublicKey;
+    let reserve: PublicKey;
+    let stakeAccount: PublicKey;
+
+    it("Stakes and unstakes through the adapter interface", async () => {
+      [pool] = PublicKey.findProgramAddressSync(
+        [Buffer.from("stake_pool"), mint.toBuffer()],
+        stakingProgram.programId
+      );
+      [reserve] = PublicKey.findProgramAddressSync(
+        [Buffer.from("stake_reserve"), pool.toBuffer()],
+        stakingProgram.programId
+      );
+      [stakeAccount] = PublicKey.findProgramAddressSync(
+        [Buffer.from("stake"), pool.toBuffer(), wallet.publicKey.toBuffer()],
+        stakingProgram.programId
+      );
+
+      await stakingProgram.methods
+        .initializePool(100, new anchor.BN(86_400)) // 1% per day
+        .accounts({ mint, authority: wallet.publicKey })
+        .rpc();
+      await stakingProgram.methods
+        .openStakeAccount(wallet.publicKey)
+        .accounts({ pool, payer: wallet.publicKey })
+        .rpc();
+      await stakingProgram.methods
+        .deposit(new anchor.BN(1_000_000))
+        .accounts({ owner: wallet.publicKey, source: userToken, pool, reserve, stakeAccount })
+        .rpc();
+
+      // No full reward period has elapsed, so shares map 1:1 to stake
+      const staked = await stakingProgram.account.stakeAccount.fetch(stakeAccount);
+      assert.equal(staked.shares.toNumber(), 1_000_000);
+
+      await stakingProgram.methods
+        .withdraw(new anchor.BN(400_000))
+        .accounts({ owner: wallet.publicKey, destination: userToken, pool, reserve, stakeAccount })
+        .rpc();
+
+      const poolAccount = await stakingProgram.account.stakePool.fetch(pool);
+      assert.equal(poolAccount.totalStaked.toNumber(), 600_000);
+      assert.equal((await stakingProgram.account.stakeAccount.fetch(stakeAccount)).shares.toNumber(), 600_000);
+    });
+  });
+});
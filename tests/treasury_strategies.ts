@@ -0,0 +1,437 @@
+import * as anchor from "@coral-xyz/anchor";
+import { Program } from "@coral-xyz/anchor";
+import { Treasury } from "../target/types/treasury";
+import { AgentRegistry } from "../target/types/agent_registry";
+import { MockLending } from "../target/types/mock_lending";
+import { MockSwap } from "../target/types/mock_swap";
+import { MockStaking } from "../target/types/mock_staking";
+import { Keypair, PublicKey } from "@solana/web3.js";
+import {
+  createMint,
+  createAccount,
+  getAccount,
+  mintTo,
+} from "@solana/spl-token";
+import { assert } from "chai";
+
+describe("Treasury Strategies", () => {
+  // Drives decisions end to end: propose, vote, approve, queue, execute through an adapter
+  anchor.setProvider(anchor.AnchorProvider.env());
+
+  const provider = anchor.getProvider() as anchor.AnchorProvider;
+  const wallet = provider.wallet as anchor.Wallet;
+  const payer = wallet.payer;
+
+  const treasuryProgram = anchor.workspace.Treasury as Program<Treasury>;
+  const registryProgram = anchor.workspace.AgentRegistry as Program<AgentRegistry>;
+  const lendingProgram = anchor.workspace.MockLending as Program<MockLending>;
+  const swapProgram = anchor.workspace.MockSwap as Program<MockSwap>;
+  const stakingProgram = anchor.workspace.MockStaking as Program<MockStaking>;
+
+  const DECISION_PERIOD = 2; // Seconds; short so tests can wait it out
+
+  const treasuryKeypair = Keypair.generate();
+  const registryKeypair = Keypair.generate();
+  const agentKeypair = Keypair.generate();
+  const treasury = treasuryKeypair.publicKey;
+  const registry = registryKeypair.publicKey;
+  const agent = agentKeypair.publicKey;
+
+  let mint: PublicKey;
+  let userToken: PublicKey;
+  let vaultAsset: PublicKey;
+  let vault: PublicKey;
+  let vaultAuthority: PublicKey;
+  let council: PublicKey;
+  let sequence = 0;
+
+  const pda = (seeds: Buffer[], programId = treasuryProgram.programId) =>
+    PublicKey.findProgramAddressSync(seeds, programId)[0];
+
+  const balance = async (account: PublicKey) =>
+    Number((await getAccount(provider.connection, account)).amount);
+
+  const sleep = (ms: number) => new Promise((resolve) => setTimeout(resolve, ms));
+
+  // Submit a decision from the test agent and carry it through to Queued
+  const queueDecision = async (decision: {
+    action: object;
+    targetProtocol: string;
+    sourceProtocol?: string;
+    amount: number;
+  }) => {
+    const seq = Buffer.alloc(8);
+    seq.writeBigUInt64LE(BigInt(sequence++));
+    const proposal = pda([Buffer.from("proposal"), treasury.toBuffer(), wallet.publicKey.toBuffer(), seq]);
+    const executionApproval = pda([Buffer.from("execution_approval"), council.toBuffer(), proposal.toBuffer()]);
+
+    await treasuryProgram.methods
+      .submitDecision(
+        {
+          agentId: agent,
+          assetMint: mint,
+          action: decision.action as any,
+          targetProtocol: decision.targetProtocol,
+          sourceProtocol: decision.sourceProtocol ?? "",
+          amount: new anchor.BN(decision.amount),
+          expectedYieldBps: 500,
+          riskScore: 10,
+        },
+        Array.from(Buffer.alloc(32, 7))
+      )
+      .accountsPartial({
+        treasury,
+        vaultAsset,
+        proposalCounter: pda([Buffer.from("proposal_counter"), treasury.toBuffer(), wallet.publicKey.toBuffer()]),
+        proposal,
+        submitterAgent: agent,
+        agent: wallet.publicKey,
+      })
+      .rpc();
+    await treasuryProgram.methods
+      .castVote(true)
+      .accountsPartial({
+        treasury,
+        proposal,
+        vote: pda([Buffer.from("vote"), proposal.toBuffer(), wallet.publicKey.toBuffer()]),
+        voterAgent: agent,
+        voter: wallet.publicKey,
+      })
+      .rpc();
+    await treasuryProgram.methods
+      .approveExecution()
+      .accountsPartial({ treasury, proposal, council, executionApproval, member: wallet.publicKey })
+      .rpc();
+
+    await sleep((DECISION_PERIOD + 1) * 1000);
+    await treasuryProgram.methods
+      .queueDecision()
+      .accountsPartial({ treasury, proposal, council, executionApproval, executor: wallet.publicKey })
+      .rpc();
+
+    return proposal;
+  };
+
+  // Whitelist an adapter for `mint` and return its layout as remaining accounts
+  const registerAdapter = async (targetProtocol: string, programId: PublicKey, accounts: [PublicKey, boolean][]) => {
+    const adapterAccounts = accounts.map(([pubkey, isWritable]) => ({ pubkey, isWritable }));
+    await treasuryProgram.methods
+      .registerStrategyAdapter(targetProtocol, programId, adapterAccounts)
+      .accountsPartial({ treasury, assetMint: mint, authority: wallet.publicKey })
+      .rpc();
+    return adapterAccounts.map((account) => ({ ...account, isSigner: false }));
+  };
+
+  const positionAddress = (targetProtocol: string) =>
+    pda([Buffer.from("position"), treasury.toBuffer(), mint.toBuffer(), Buffer.from(targetProtocol)]);
+
+  const executeAccounts = (proposal: PublicKey, targetProtocol: string, adapterProgram: PublicKey) => ({
+    treasury,
+    proposal,
+    council,
+    executor: wallet.publicKey,
+    vaultAsset,
+    vault,
+    vaultAuthority,
+    strategyAdapter: pda([Buffer.from("strategy_adapter"), treasury.toBuffer(), mint.toBuffer(), Buffer.from(targetProtocol)]),
+    position: positionAddress(targetProtocol),
+    adapterProgram,
+    sourceAdapter: null,
+    sourcePosition: null,
+    sourceAdapterProgram: null,
+  });
+
+  before(async () => {
+    await registryProgram.methods
+      .initialize({
+        minStake: new anchor.BN(1_000_000),
+        reputationDecayRate: 100,
+        taskTimeoutSlashBps: 500,
+      })
+      .accounts({ registry, authority: wallet.publicKey })
+      .signers([registryKeypair])
+      .rpc();
+    await registryProgram.methods
+      .registerAgent({ arbiter: {} }, ["execution"])
+      .accounts({ registry, agent, owner: wallet.publicKey })
+      .signers([agentKeypair])
+      .rpc();
+
+    await treasuryProgram.methods
+      .initialize({
+        minDeposit: new anchor.BN(1),
+        maxAllocationBps: 5000,
+        decisionPeriod: new anchor.BN(DECISION_PERIOD),
+        quorumThreshold: new anchor.BN(1),
+        approvalMode: { simpleMajority: {} },
+        proposalRetention: new anchor.BN(0),
+        maxRiskScore: 100,
+        epochRiskBudget: new anchor.BN(1_000),
+        minProposerReputation: 0,
+        minVoterReputation: 0,
+        allowedProposerTypes: 0b11111, // Any agent type
+        epochDuration: new anchor.BN(604_800),
+        executionDelay: new anchor.BN(0), // Executable as soon as queued
+        executionGracePeriod: new anchor.BN(3_600),
+        epochOutflowCap: new anchor.BN(0),
+        epochOutflowCapBps: 0,
+        dailyOutflowCap: new anchor.BN(0),
+        dailyOutflowCapBps: 0,
+        managementFeeBps: 0,
+        performanceFeeBps: 0,
+        requireReasoningReveal: false,
+      })
+      .accounts({ treasury, registry, authority: wallet.publicKey })
+      .signers([treasuryKeypair])
+      .rpc();
+
+    mint = await createMint(provider.connection, payer, wallet.publicKey, null, 6);
+    userToken = await createAccount(provider.connection, payer, mint, wallet.publicKey);
+    await mintTo(provider.connection, payer, mint, userToken, payer, 10_000_000);
+
+    vaultAsset = pda([Buffer.from("vault_asset"), treasury.toBuffer(), mint.toBuffer()]);
+    vault = pda([Buffer.from("vault"), treasury.toBuffer(), mint.toBuffer()]);
+    vaultAuthority = pda([Buffer.from("vault_authority"), treasury.toBuffer()]);
+    council = pda([Buffer.from("council"), treasury.toBuffer()]);
+    const shareMint = pda([Buffer.from("share_mint"), treasury.toBuffer(), mint.toBuffer()]);
+
+    await treasuryProgram.methods
+      .whitelistAsset()
+      .accountsPartial({ treasury, assetMint: mint, vaultAsset, vaultAuthority, vault, shareMint, authority: wallet.publicKey })
+      .rpc();
+
+    // Fee shares go to the authority, in an account separate from its deposit shares
+    const userShares = await createAccount(provider.connection, payer, shareMint, wallet.publicKey);
+    const feeShares = await createAccount(provider.connection, payer, shareMint, wallet.publicKey, Keypair.generate());
+    await treasuryProgram.methods
+      .deposit(new anchor.BN(4_000_000))
+      .accountsPartial({
+        treasury,
+        vaultAsset,
+        user: wallet.publicKey,
+        userTokenAccount: userToken,
+        treasuryTokenAccount: vault,
+        vaultAuthority,
+        shareMint,
+        userShareAccount: userShares,
+        feeShareAccount: feeShares,
+      })
+      .rpc();
+
+    await treasuryProgram.methods
+      .initializeCouncil([wallet.publicKey], 1)
+      .accountsPartial({ treasury, council, authority: wallet.publicKey })
+      .rpc();
+  });
+
+  describe("Mock Lending", () => {
+    let market: PublicKey;
+    let reserve: PublicKey;
+    let receipt: PublicKey;
+    let proposal: PublicKey;
+    let layout: { pubkey: PublicKey; isWritable: boolean; isSigner: boolean }[];
+
+    it("Whitelists the lending adapter and queues a deposit decision", async () => {
+      market = pda([Buffer.from("market"), mint.toBuffer()], lendingProgram.programId);
+      reserve = pda([Buffer.from("reserve"), market.toBuffer()], lendingProgram.programId);
+      receipt = pda([Buffer.from("receipt"), market.toBuffer(), vaultAuthority.toBuffer()], lendingProgram.programId);
+
+      await lendingProgram.methods
+        .initializeMarket(500)
+        .accounts({ mint, authority: wallet.publicKey })
+        .rpc();
+      // The vault authority owns the receipt, so the treasury can sign for it
+      await lendingProgram.methods
+        .openReceipt(vaultAuthority)
+        .accounts({ market, payer: wallet.publicKey })
+        .rpc();
+
+      layout = await registerAdapter("lending", lendingProgram.programId, [
+        [market, true],
+        [reserve, true],
+        [receipt, true],
+      ]);
+
+      proposal = await queueDecision({ action: { deposit: {} }, targetProtocol: "lending", amount: 1_000_000 });
+      const queued = await treasuryProgram.account.proposal.fetch(proposal);
+      assert.deepEqual(queued.status, { queued: {} });
+    });
+
+    it("Rejects remaining accounts that do not match the adapter layout", async () => {
+      // Same accounts, wrong order: reserve and receipt swapped
+      const swapped = [layout[0], layout[2], layout[1]];
+      try {
+        await treasuryProgram.methods
+          .executeQueued()
+          .accountsPartial(executeAccounts(proposal, "lending", lendingProgram.programId))
+          .remainingAccounts(swapped)
+          .rpc();
+        assert.fail("Execution with a mismatched layout should fail");
+      } catch (e) {
+        assert.equal(e.error?.errorCode?.code, "InvalidAdapterAccounts");
+      }
+
+      const stillQueued = await treasuryProgram.account.proposal.fetch(proposal);
+      assert.deepEqual(stillQueued.status, { queued: {} });
+    });
+
+    it("Executes the decision and moves funds into the position", async () => {
+      await treasuryProgram.methods
+        .executeQueued()
+        .accountsPartial(executeAccounts(proposal, "lending", lendingProgram.programId))
+        .remainingAccounts(layout)
+        .rpc();
+
+      const executed = await treasuryProgram.account.proposal.fetch(proposal);
+      assert.deepEqual(executed.status, { executed: {} });
+
+      const position = await treasuryProgram.account.position.fetch(positionAddress("lending"));
+      assert.equal(position.principal.toNumber(), 1_000_000);
+      assert.equal(position.markedValue.toNumber(), 1_000_000);
+
+      const asset = await treasuryProgram.account.vaultAsset.fetch(vaultAsset);
+      assert.equal(asset.balance.toNumber(), 3_000_000);
+      assert.equal(asset.deployed.toNumber(), 1_000_000);
+      assert.equal(await balance(vault), 3_000_000);
+      assert.equal(await balance(reserve), 1_000_000);
+      assert.equal((await lendingProgram.account.receipt.fetch(receipt)).balance.toNumber(), 1_000_000);
+    });
+  });
+
+  describe("Mock Swap", () => {
+    let outputMint: PublicKey;
+    let pool: PublicKey;
+    let inputReserve: PublicKey;
+    let holding: PublicKey;
+    let layout: { pubkey: PublicKey; isWritable: boolean; isSigner: boolean }[];
+
+    it("Deploys into a swap holding at the pool rate", async () => {
+      outputMint = await createMint(provider.connection, payer, wallet.publicKey, null, 6);
+      const userOutput = await createAccount(provider.connection, payer, outputMint, wallet.publicKey);
+      await mintTo(provider.connection, payer, outputMint, userOutput, payer, 5_000_000);
+
+      pool = pda([Buffer.from("pool"), mint.toBuffer(), outputMint.toBuffer()], swapProgram.programId);
+      inputReserve = pda([Buffer.from("input_reserve"), pool.toBuffer()], swapProgram.programId);
+      const outputReserve = pda([Buffer.from("output_reserve"), pool.toBuffer()], swapProgram.programId);
+      holding = pda([Buffer.from("holding"), pool.toBuffer(), vaultAuthority.toBuffer()], swapProgram.programId);
+
+      await swapProgram.methods
+        .initializePool(new anchor.BN(2_000_000)) // 2 output per input
+        .accounts({ inputMint: mint, outputMint, authority: wallet.publicKey })
+        .rpc();
+      await swapProgram.methods
+        .openHolding(vaultAuthority)
+        .accounts({ pool, payer: wallet.publicKey })
+        .rpc();
+      await swapProgram.methods
+        .fundReserve(new anchor.BN(5_000_000))
+        .accounts({ pool, reserve: outputReserve, source: userOutput, funder: wallet.publicKey })
+        .rpc();
+
+      layout = await registerAdapter("swap", swapProgram.programId, [
+        [pool, true],
+        [inputReserve, true],
+        [outputReserve, false],
+        [holding, true],
+      ]);
+
+      const proposal = await queueDecision({ action: { deposit: {} }, targetProtocol: "swap", amount: 1_000_000 });
+      await treasuryProgram.methods
+        .executeQueued()
+        .accountsPartial(executeAccounts(proposal, "swap", swapProgram.programId))
+        .remainingAccounts(layout)
+        .rpc();
+
+      assert.equal((await swapProgram.account.holding.fetch(holding)).outputBalance.toNumber(), 2_000_000);
+      const position = await treasuryProgram.account.position.fetch(positionAddress("swap"));
+      assert.equal(position.principal.toNumber(), 1_000_000);
+      const asset = await treasuryProgram.account.vaultAsset.fetch(vaultAsset);
+      assert.equal(asset.deployed.toNumber(), 2_000_000);
+      assert.equal(await balance(vault), asset.balance.toNumber());
+    });
+
+    it("Realizes the gain when the output appreciates", async () => {
+      // Output doubles in value; top up the input side so the gain can be paid out
+      await swapProgram.methods
+        .setRate(new anchor.BN(1_000_000))
+        .accounts({ pool, authority: wallet.publicKey })
+        .rpc();
+      await swapProgram.methods
+        .fundReserve(new anchor.BN(200_000))
+        .accounts({ pool, reserve: inputReserve, source: userToken, funder: wallet.publicKey })
+        .rpc();
+
+      const before = await treasuryProgram.account.vaultAsset.fetch(vaultAsset);
+      const proposal = await queueDecision({ action: { withdraw: {} }, targetProtocol: "swap", amount: 1_200_000 });
+      await treasuryProgram.methods
+        .executeQueued()
+        .accountsPartial(executeAccounts(proposal, "swap", swapProgram.programId))
+        .remainingAccounts(layout)
+        .rpc();
+
+      const position = await treasuryProgram.account.position.fetch(positionAddress("swap"));
+      assert.equal(position.principal.toNumber(), 0);
+      assert.equal(position.markedValue.toNumber(), 0);
+
+      const asset = await treasuryProgram.account.vaultAsset.fetch(vaultAsset);
+      assert.equal(asset.balance.toNumber() - before.balance.toNumber(), 1_200_000);
+      assert.equal(before.deployed.toNumber() - asset.deployed.toNumber(), 1_000_000);
+      assert.equal(asset.epochRealizedYield.toNumber(), 200_000);
+      assert.equal(await balance(vault), asset.balance.toNumber());
+    });
+  });
+
+  describe("Mock Staking", () => {
+    let pool: PublicKey;
+    let stakeAccount: PublicKey;
+    let layout: { pubkey: PublicKey; isWritable: boolean; isSigner: boolean }[];
+
+    it("Stakes and partially unstakes through queued decisions", async () => {
+      pool = pda([Buffer.from("stake_pool"), mint.toBuffer()], stakingProgram.programId);
+      const reserve = pda([Buffer.from("stake_reserve"), pool.toBuffer()], stakingProgram.programId);
+      stakeAccount = pda([Buffer.from("stake"), pool.toBuffer(), vaultAuthority.toBuffer()], stakingProgram.programId);
+
+      await stakingProgram.methods
+        .initializePool(100, new anchor.BN(86_400)) // 1% per day
+        .accounts({ mint, authority: wallet.publicKey })
+        .rpc();
+      await stakingProgram.methods
+        .openStakeAccount(vaultAuthority)
+        .accounts({ pool, payer: wallet.publicKey })
+        .rpc();
+
+      layout = await registerAdapter("staking", stakingProgram.programId, [
+        [pool, true],
+        [reserve, true],
+        [stakeAccount, true],
+      ]);
+      const position = positionAddress("staking");
+
+      const stake = await queueDecision({ action: { deposit: {} }, targetProtocol: "staking", amount: 500_000 });
+      await treasuryProgram.methods
+        .executeQueued()
+        .accountsPartial(executeAccounts(stake, "staking", stakingProgram.programId))
+        .remainingAccounts(layout)
+        .rpc();
+
+      // No reward period has elapsed, so shares map 1:1 to stake
+      assert.equal((await stakingProgram.account.stakeAccount.fetch(stakeAccount)).shares.toNumber(), 500_000);
+      assert.equal((await treasuryProgram.account.position.fetch(position)).principal.toNumber(), 500_000);
+
+      const unstake = await queueDecision({ action: { withdraw: {} }, targetProtocol: "staking", amount: 200_000 });
+      await treasuryProgram.methods
+        .executeQueued()
+        .accountsPartial(executeAccounts(unstake, "staking", stakingProgram.programId))
+        .remainingAccounts(layout)
+        .rpc();
+
+      assert.equal((await stakingProgram.account.stakePool.fetch(pool)).totalStaked.toNumber(), 300_000);
+      const remaining = await treasuryProgram.account.position.fetch(position);
+      assert.equal(remaining.principal.toNumber(), 300_000);
+      assert.equal(remaining.markedValue.toNumber(), 300_000);
+
+      const asset = await treasuryProgram.account.vaultAsset.fetch(vaultAsset);
+      assert.equal(await balance(vault), asset.balance.toNumber());
+    });
+  });
+});
@@ -17,6 +17,23 @@ mod tests {
             max_allocation_bps: 5000, // 50%
             decision_period: 3600, // 1 hour
             quorum_threshold: 100,
+            approval_mode: ApprovalMode::SimpleMajority,
+            proposal_retention: 2_592_000, // 30 days
+            max_risk_score: 70,
+            epoch_risk_budget: 300,
+            min_proposer_reputation: 600,
+            min_voter_reputation: 600,
+            allowed_proposer_types: 0b10100, // Arbiter | Oracle
+            epoch_duration: 604_800, // 7 days
+            execution_delay: 86_400, // 1 day
+            execution_grace_period: 259_200, // 3 days
+            epoch_outflow_cap: 0, // No absolute cap
+            epoch_outflow_cap_bps: 2000, // 20% of each asset's holdings
+            daily_outflow_cap: 0,
+            daily_outflow_cap_bps: 1000, // 10% of each asset's holdings
+            management_fee_bps: 200, // 2% per year
+            performance_fee_bps: 2000, // 20% above high-water mark
+            require_reasoning_reveal: false,
         };
         
         // Assert params are stored correctly
@@ -28,8 +45,10 @@ mod tests {
     fn test_yield_decision_creation() {
         let decision = YieldDecision {
             agent_id: Pubkey::default(),
+            asset_mint: Pubkey::default(),
             action: YieldAction::Deposit,
             target_protocol: "jupiter".to_string(),
+            source_protocol: String::new(),
             amount: 100_000_000, // 100 USDC
             expected_yield_bps: 1250, // 12.5% APY
             risk_score: 30, // Low risk